@@ -27,14 +27,14 @@ impl Render for Counter {
         let theme = cx.theme();
 
         div()
-            .p_4()
+            .p(theme.spacing.xl)
             .flex()
-            .gap_4()
+            .gap(theme.spacing.xl)
             .size_full()
             .justify_center()
             .items_center()
             .bg(theme.ground)
-            .gap_4()
+            .text_color(theme.foreground)
             .child(
                 div()
                     .text_2xl()
@@ -59,7 +59,10 @@ fn main() {
                 window_bounds: Some(WindowBounds::Windowed(bounds)),
                 ..Default::default()
             },
-            |_, cx| cx.new(|_| Counter::new(0)),
+            |window, cx| {
+                Theme::observe_window(window);
+                cx.new(|_| Counter::new(0))
+            },
         )
         .unwrap();
 
@@ -3,50 +3,11 @@ mod assets;
 use assets::Assets;
 use gpui::{prelude::*, *};
 use ui::{
-    Backspace, Button, ButtonVariant, Copy, Cut, Delete, Dropdown, End, Home, Left, MenuItem,
-    Paste, Right, SelectAll, SelectLeft, SelectRight, TextInput, Theme, ThemeAble,
+    Button, ButtonVariant, Date, DatePicker, DatePickerEvent, Dialog, Select, Theme, ThemeAble,
+    Toast, ToastStack, Tooltip,
 };
 
-fn parse_date(date: SharedString) -> bool {
-    let parts: Vec<&str> = date.split('.').collect();
-    if parts.len() != 3 {
-        return false;
-    }
-
-    let day: u16 = match parts[0].parse() {
-        Ok(v) => v,
-        Err(_) => return false,
-    };
-    let month: u16 = match parts[1].parse() {
-        Ok(v) => v,
-        Err(_) => return false,
-    };
-    let year: u16 = match parts[2].parse() {
-        Ok(v) => v,
-        Err(_) => return false,
-    };
-
-    if !(2025..=9999).contains(&year) {
-        return false;
-    }
-    if month == 0 || month > 12 {
-        return false;
-    }
-    if day == 0 {
-        return false;
-    }
-
-    let max_day = match month {
-        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-        4 | 6 | 9 | 11 => 30,
-        2 => 28,
-        _ => return false,
-    };
-
-    day <= max_day
-}
-
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 enum FlightType {
     OneWayFlight,
     ReturnFlight,
@@ -54,115 +15,164 @@ enum FlightType {
 
 struct MainWindow {
     flight_type: FlightType,
-    dropdown_open: bool,
-    start_input: Entity<TextInput>,
-    return_input: Entity<TextInput>,
+    start_picker: Entity<DatePicker>,
+    return_picker: Entity<DatePicker>,
+    start_date: Option<Date>,
+    return_date: Option<Date>,
+    confirmation: Option<Entity<Dialog>>,
 }
 
-impl Render for MainWindow {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let start_input_content = self.start_input.read(cx).content.clone();
-        let return_input_content = self.return_input.read(cx).content.clone();
+impl MainWindow {
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let start_picker = cx.new(|cx| DatePicker::new(window, cx).min(Date::today()));
+        let return_picker = cx.new(|cx| {
+            let mut picker = DatePicker::new(window, cx).min(Date::today());
+            picker.disabled(true, cx);
+            picker
+        });
 
-        let start_ok = start_input_content.is_empty() || parse_date(start_input_content.clone());
-        let return_ok = return_input_content.is_empty() || parse_date(return_input_content.clone());
+        cx.subscribe(&start_picker, |this, _, event, cx| {
+            let DatePickerEvent::Changed(date) = *event;
+            this.start_date = date;
+            this.return_picker.update(cx, |picker, cx| {
+                picker.set_min(Some(date.unwrap_or_else(Date::today)), cx)
+            });
+            this.update_highlight(cx);
+        })
+        .detach();
+        cx.subscribe(&return_picker, |this, _, event, cx| {
+            let DatePickerEvent::Changed(date) = *event;
+            this.return_date = date;
+            this.update_highlight(cx);
+        })
+        .detach();
+
+        Self {
+            flight_type: FlightType::OneWayFlight,
+            start_picker,
+            return_picker,
+            start_date: None,
+            return_date: None,
+            confirmation: None,
+        }
+    }
 
-        let should_disable = self.flight_type == FlightType::OneWayFlight;
-        self.return_input.update(cx, |this, _cx| {
-            this.disabled(should_disable);
-        });
+    fn set_flight_type(&mut self, flight_type: FlightType, cx: &mut Context<Self>) {
+        let one_way = flight_type == FlightType::OneWayFlight;
+        self.flight_type = flight_type;
+        self.return_picker
+            .update(cx, |picker, cx| picker.disabled(one_way, cx));
+        self.update_highlight(cx);
+    }
 
-        let theme = cx.theme();
+    fn update_highlight(&self, cx: &mut Context<Self>) {
+        let highlight = match (self.start_date, self.return_date) {
+            (Some(start), Some(end)) if self.flight_type == FlightType::ReturnFlight => {
+                Some(start..=end)
+            }
+            _ => None,
+        };
+        for picker in [&self.start_picker, &self.return_picker] {
+            picker.update(cx, |picker, cx| picker.set_highlight(highlight.clone(), cx));
+        }
+        cx.notify();
+    }
 
-        let dropdown_trigger = Button::new("dropdown-trigger".into())
-            .variant(ButtonVariant::Outlined)
-            .with_label(match self.flight_type {
-                FlightType::OneWayFlight => "One way flight".into(),
-                FlightType::ReturnFlight => "Return flight".into(),
-            })
-            .with_icon("icons/caret_down.svg".into())
-            .on_click(cx.listener(|this, _event, _window, cx| {
-                this.dropdown_open = !this.dropdown_open;
+    fn confirm_booking(&mut self, message: String, window: &mut Window, cx: &mut Context<Self>) {
+        let dialog = cx.new(|cx| {
+            Dialog::new("Confirm booking".into(), cx)
+                .body(message.into())
+                .action("Cancel".into(), ButtonVariant::Outlined)
+                .action("Book".into(), ButtonVariant::Solid)
+                .default_action(1)
+        });
+        let response = dialog.update(cx, |dialog, cx| dialog.open(window, cx));
+        self.confirmation = Some(dialog);
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let response = response.await.ok().flatten();
+            this.update(cx, |this, cx| {
+                this.confirmation = None;
+                if response == Some(1) {
+                    Toast::success("Booking saved").show(cx);
+                }
                 cx.notify();
-            }));
-
-        let menu = vec![
-            MenuItem::new("1".into(), "One-way flight".into()).on_click(cx.listener(
-                |this, _event, _window, cx| {
-                    this.dropdown_open = !this.dropdown_open;
-                    this.flight_type = FlightType::OneWayFlight;
-                    cx.stop_propagation();
-                    cx.notify();
-                },
-            )),
-            MenuItem::new("2".into(), "Return flight".into()).on_click(cx.listener(
-                |this, _event, _window, cx| {
-                    this.dropdown_open = !this.dropdown_open;
-                    this.flight_type = FlightType::ReturnFlight;
-                    cx.stop_propagation();
-                    cx.notify();
-                },
-            )),
-        ];
+            })
+            .ok();
+        })
+        .detach();
+    }
+}
+
+impl Render for MainWindow {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let start_date = self.start_date;
+        let return_date = self.return_date;
+        let unavailable = if start_date.is_none() {
+            Some("Enter a valid departure date")
+        } else if self.flight_type == FlightType::ReturnFlight && return_date.is_none() {
+            Some("Enter a valid return date on or after the departure")
+        } else {
+            None
+        };
+
+        let theme = cx.theme();
 
         div()
             .id("main-window")
             .flex()
             .flex_col()
-            .gap_2()
-            .p_2()
+            .gap(theme.spacing.md)
+            .p(theme.spacing.md)
             .bg(theme.ground)
             .size_full()
             .justify_start()
             .items_start()
             .text_color(theme.foreground)
             .child(
-                Dropdown::new(
-                    "dropdown-id".into(),
-                    dropdown_trigger.into_any_element(),
-                    menu,
+                Select::new(
+                    "flight-type".into(),
+                    vec![
+                        (FlightType::OneWayFlight, "One-way flight".into()),
+                        (FlightType::ReturnFlight, "Return flight".into()),
+                    ],
                 )
-                .open(self.dropdown_open),
-            )
-            .child(
-                div()
-                    .w_full()
-                    .rounded_md()
-                    .when(!start_ok, |this| this.bg(red()))
-                    .child(self.start_input.clone()),
+                .value(Some(self.flight_type))
+                .on_change(cx.listener(
+                    |this, flight_type: &FlightType, _window, cx| {
+                        this.set_flight_type(*flight_type, cx);
+                    },
+                )),
             )
+            .child(self.start_picker.clone())
+            .child(self.return_picker.clone())
             .child(
-                div()
-                    .w_full()
-                    .rounded_md()
-                    .when(!return_ok, |this| this.bg(red()))
-                    .child(self.return_input.clone()),
-            )
-            .child(
-                Button::new("book-flight".into())
-                    .full_width(true)
-                    .with_label("Book".into())
-                    .disabled(
-                        start_input_content.is_empty()
-                            || (self.flight_type == FlightType::ReturnFlight
-                                && !parse_date(return_input_content.clone()))
-                            || !start_ok,
-                    )
-                    .on_click(cx.listener(move |this, _event, window, cx| {
-                        let msg = match this.flight_type {
-                            FlightType::OneWayFlight => format!(
-                                "You have booked a one-way flight on {}.",
-                                start_input_content
-                            ),
-                            FlightType::ReturnFlight => format!(
-                                "You have booked a return flight on {}, and will return on {}",
-                                start_input_content, return_input_content
-                            ),
-                        };
-
-                        let _ = window.prompt(PromptLevel::Info, &msg, None, &["Ok", "Cancel"], cx);
-                    })),
+                Tooltip::new(
+                    "book-flight-tooltip",
+                    Button::new("book-flight".into())
+                        .full_width(true)
+                        .with_label("Book".into())
+                        .disabled(unavailable.is_some())
+                        .on_click(cx.listener(move |this, _event, window, cx| {
+                            let message = match (&this.flight_type, start_date, return_date) {
+                                (FlightType::OneWayFlight, Some(start), _) => {
+                                    format!("Book a one-way flight on {start}?")
+                                }
+                                (FlightType::ReturnFlight, Some(start), Some(end)) => {
+                                    format!("Book a return flight on {start}, returning on {end}?")
+                                }
+                                _ => return,
+                            };
+
+                            this.confirm_booking(message, window, cx);
+                        })),
+                    unavailable.unwrap_or("Book this flight").into(),
+                )
+                .full_width(true),
             )
+            .children(self.confirmation.clone())
+            .child(ToastStack::new())
     }
 }
 
@@ -172,24 +182,15 @@ fn main() {
             base: "assets".into(),
         })
         .run(|cx: &mut App| {
-            let bounds = Bounds::centered(None, size(px(200.), px(160.0)), cx);
-
-            Theme::init(cx);
-
-            cx.bind_keys([
-                KeyBinding::new("backspace", Backspace, None),
-                KeyBinding::new("delete", Delete, None),
-                KeyBinding::new("left", Left, None),
-                KeyBinding::new("right", Right, None),
-                KeyBinding::new("shift-left", SelectLeft, None),
-                KeyBinding::new("shift-right", SelectRight, None),
-                KeyBinding::new("ctrl-a", SelectAll, None),
-                KeyBinding::new("ctrl-v", Paste, None),
-                KeyBinding::new("ctrl-c", Copy, None),
-                KeyBinding::new("ctrl-x", Cut, None),
-                KeyBinding::new("home", Home, None),
-                KeyBinding::new("end", End, None),
-            ]);
+            let bounds = Bounds::centered(None, size(px(260.), px(360.0)), cx);
+
+            ui::init(cx);
+            if let Err(errors) = ui::load_keymap_file("keymap.json", cx) {
+                for error in errors {
+                    eprintln!("keymap.json: {error}");
+                }
+            }
+            Theme::watch("theme.toml", cx, |error| eprintln!("theme.toml: {error}")).detach();
 
             cx.open_window(
                 WindowOptions {
@@ -200,15 +201,9 @@ fn main() {
                     window_bounds: Some(WindowBounds::Windowed(bounds)),
                     ..Default::default()
                 },
-                |_, cx| {
-                    cx.new(|cx| MainWindow {
-                        flight_type: FlightType::OneWayFlight,
-                        dropdown_open: false,
-                        start_input: cx
-                            .new(|cx| TextInput::new(cx).placeholder("DD.MM.YYYY".into())),
-                        return_input: cx
-                            .new(|cx| TextInput::new(cx).placeholder("DD.MM.YYYY".into())),
-                    })
+                |window, cx| {
+                    Theme::observe_window(window);
+                    cx.new(|cx| MainWindow::new(window, cx))
                 },
             )
             .unwrap();
@@ -1,15 +1,12 @@
 use gpui::{
     App, Application, Bounds, Context, Entity, EventEmitter, FocusHandle, Focusable, KeyBinding,
-    SharedString, Window, WindowBounds, WindowOptions, div, prelude::*, px, size,
-};
-use ui::{
-    Backspace, Copy, Cut, Delete, End, Home, Left, Paste, Quit, Right, SelectAll, SelectLeft,
-    SelectRight, TextInput, Theme, ThemeAble,
+    Window, WindowBounds, WindowOptions, div, prelude::*, px, size,
 };
+use ui::{NumberInput, NumberInputEvent, Quit, Theme, ThemeAble};
 
 struct ConversionModel {
-    c_val: f32,
-    f_val: f32,
+    c_val: f64,
+    f_val: f64,
 }
 
 impl ConversionModel {
@@ -20,19 +17,19 @@ impl ConversionModel {
         }
     }
 
-    fn c_to_f(c: f32) -> f32 {
+    fn c_to_f(c: f64) -> f64 {
         (c * 1.8) + 32.
     }
 
-    fn f_to_c(f: f32) -> f32 {
+    fn f_to_c(f: f64) -> f64 {
         (f - 32.) * (5. / 9.)
     }
 }
 
 struct TemperatureConverterApp {
     model: Entity<ConversionModel>,
-    celsius_input: Entity<TextInput>,
-    fahrenheit_input: Entity<TextInput>,
+    celsius_input: Entity<NumberInput>,
+    fahrenheit_input: Entity<NumberInput>,
     focus_handle: FocusHandle,
 }
 
@@ -44,9 +41,12 @@ impl Focusable for TemperatureConverterApp {
 
 impl Render for TemperatureConverterApp {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+
         div()
             .size_full()
-            .bg(cx.theme().ground)
+            .bg(theme.ground)
+            .text_color(theme.foreground)
             .track_focus(&self.focus_handle(cx))
             .flex()
             .flex_col()
@@ -55,55 +55,22 @@ impl Render for TemperatureConverterApp {
             .child(
                 div()
                     .size_full()
-                    .p_2()
-                    .gap_2()
+                    .p(theme.spacing.md)
+                    .gap(theme.spacing.md)
                     .flex()
                     .items_center()
                     .child(
                         div()
                             .w_full()
-                            .p_2()
-                            .rounded_md()
-                            .child(self.celsius_input.clone())
-                            .on_key_up(cx.listener(|app, _event, _window, cx| {
-                                app.model.update(cx, |this, cx| {
-                                    this.c_val = match app.celsius_input.read(cx).content.parse() {
-                                        Ok(val) => val,
-                                        Err(_) => {
-                                            return;
-                                        }
-                                    };
-                                    cx.emit(ChangeEvent {
-                                        c_changed: true,
-                                        f_changed: false,
-                                    });
-                                    // cx.notify();
-                                });
-                            })),
+                            .p(theme.spacing.md)
+                            .child(self.celsius_input.clone()),
                     )
                     .child("Celsius = ")
                     .child(
                         div()
                             .w_full()
-                            .p_2()
-                            .rounded_md()
-                            .child(self.fahrenheit_input.clone())
-                            .on_key_up(cx.listener(|app, _, _, cx| {
-                                app.model.update(cx, |this, cx| {
-                                    this.f_val = match app.fahrenheit_input.read(cx).content.parse()
-                                    {
-                                        Ok(val) => val,
-                                        Err(_) => {
-                                            return;
-                                        }
-                                    };
-                                    cx.emit(ChangeEvent {
-                                        c_changed: false,
-                                        f_changed: true,
-                                    });
-                                    // cx.notify();
-                                });
-                            })),
+                            .p(theme.spacing.md)
+                            .child(self.fahrenheit_input.clone()),
                     )
                     .child(" Fahrenheit"),
             )
@@ -118,22 +85,13 @@ impl EventEmitter<ChangeEvent> for ConversionModel {}
 
 fn main() {
     Application::new().run(|cx: &mut App| {
-        Theme::init(cx);
-
-        cx.bind_keys([
-            KeyBinding::new("backspace", Backspace, None),
-            KeyBinding::new("delete", Delete, None),
-            KeyBinding::new("left", Left, None),
-            KeyBinding::new("right", Right, None),
-            KeyBinding::new("shift-left", SelectLeft, None),
-            KeyBinding::new("shift-right", SelectRight, None),
-            KeyBinding::new("ctrl-a", SelectAll, None),
-            KeyBinding::new("ctrl-v", Paste, None),
-            KeyBinding::new("ctrl-c", Copy, None),
-            KeyBinding::new("ctrl-x", Cut, None),
-            KeyBinding::new("home", Home, None),
-            KeyBinding::new("end", End, None),
-        ]);
+        ui::init(cx);
+        if let Err(errors) = ui::load_keymap_file("keymap.json", cx) {
+            for error in errors {
+                eprintln!("keymap.json: {error}");
+            }
+        }
+        Theme::watch("theme.toml", cx, |error| eprintln!("theme.toml: {error}")).detach();
 
         let bounds = Bounds::centered(None, size(px(500.0), px(100.0)), cx);
         let window = cx
@@ -142,45 +100,77 @@ fn main() {
                     window_bounds: Some(WindowBounds::Windowed(bounds)),
                     ..Default::default()
                 },
-                |_, cx| {
+                |window, cx| {
+                    Theme::observe_window(window);
                     let model = cx.new(|_| ConversionModel::new());
                     let c_input = cx.new(|cx| {
                         cx.subscribe(
                             &model,
-                            |this: &mut TextInput, model: Entity<ConversionModel>, event, cx| {
+                            |this: &mut NumberInput, model: Entity<ConversionModel>, event, cx| {
                                 if event.f_changed {
-                                    this.content = SharedString::from(
-                                        ConversionModel::f_to_c(model.read(cx).f_val).to_string(),
+                                    this.set_value(
+                                        ConversionModel::f_to_c(model.read(cx).f_val),
+                                        cx,
                                     );
                                 };
-                                cx.notify();
                             },
                         )
                         .detach();
-                        TextInput::new(cx)
+                        NumberInput::new(window, cx).min(-273.15).precision(1)
                     });
                     let f_input = cx.new(|cx| {
                         cx.subscribe(
                             &model,
-                            |this: &mut TextInput, model: Entity<ConversionModel>, event, cx| {
+                            |this: &mut NumberInput, model: Entity<ConversionModel>, event, cx| {
                                 if event.c_changed {
-                                    this.content = SharedString::from(
-                                        ConversionModel::c_to_f(model.read(cx).c_val).to_string(),
+                                    this.set_value(
+                                        ConversionModel::c_to_f(model.read(cx).c_val),
+                                        cx,
                                     );
                                 };
-                                cx.notify();
                             },
                         )
                         .detach();
 
-                        TextInput::new(cx)
+                        NumberInput::new(window, cx).min(-459.67).precision(1)
                     });
 
-                    cx.new(|cx| TemperatureConverterApp {
-                        celsius_input: c_input,
-                        fahrenheit_input: f_input,
-                        focus_handle: cx.focus_handle(),
-                        model,
+                    cx.new(|cx| {
+                        cx.subscribe(
+                            &c_input,
+                            |app: &mut TemperatureConverterApp, _, event, cx| {
+                                let NumberInputEvent::ValueChanged(val) = *event;
+                                app.model.update(cx, |this, cx| {
+                                    this.c_val = val;
+                                    cx.emit(ChangeEvent {
+                                        c_changed: true,
+                                        f_changed: false,
+                                    });
+                                });
+                            },
+                        )
+                        .detach();
+                        cx.subscribe(
+                            &f_input,
+                            |app: &mut TemperatureConverterApp, _, event, cx| {
+                                let NumberInputEvent::ValueChanged(val) = *event;
+                                app.model.update(cx, |this, cx| {
+                                    this.f_val = val;
+                                    cx.emit(ChangeEvent {
+                                        c_changed: false,
+                                        f_changed: true,
+                                    });
+                                });
+                            },
+                        )
+                        .detach();
+
+                        TemperatureConverterApp {
+                            celsius_input: c_input,
+                            fahrenheit_input: f_input,
+                            focus_handle: cx.focus_handle(),
+                            model,
+                        }
                     })
                 },
             )
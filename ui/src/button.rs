@@ -1,21 +1,19 @@
 use gpui::{
     App, ClickEvent, CursorStyle, InteractiveElement, IntoElement, ParentElement, RenderOnce,
-    SharedString, StatefulInteractiveElement, Styled, Window, div, prelude::*, rgb, svg,
+    SharedString, StatefulInteractiveElement, Styled, Window, div, prelude::*, svg,
 };
 
 use crate::theme::ThemeAble;
 
+#[derive(Clone, Copy, Default, PartialEq)]
 pub enum ButtonVariant {
     Ghost,
     Outlined,
+    #[default]
     Solid,
 }
 
-impl Default for ButtonVariant {
-    fn default() -> Self {
-        Self::Solid
-    }
-}
+pub(crate) type ClickHandler = Box<dyn Fn(&ClickEvent, &mut Window, &mut App)>;
 
 #[derive(Default, IntoElement)]
 pub struct Button {
@@ -24,8 +22,9 @@ pub struct Button {
     variant: ButtonVariant,
     label: Option<SharedString>,
     icon_path: Option<SharedString>,
-    on_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
+    on_click: Option<ClickHandler>,
     full_width: bool,
+    focused: bool,
 }
 
 impl Button {
@@ -38,6 +37,7 @@ impl Button {
             icon_path: None,
             on_click: None,
             full_width: false,
+            focused: false,
         }
     }
 
@@ -51,6 +51,13 @@ impl Button {
         self
     }
 
+    /// Draws the focus ring, for containers that move keyboard focus between
+    /// their buttons themselves.
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
     pub fn variant(mut self, variant: ButtonVariant) -> Self {
         self.variant = variant;
         self
@@ -85,20 +92,20 @@ impl RenderOnce for Button {
             .flex()
             .items_center()
             .justify_center()
-            .gap_4()
+            .gap(theme.spacing.xl)
             .text_center()
             .h_8()
-            .px_3()
-            .rounded_md()
+            .px(theme.spacing.lg)
+            .rounded(theme.radius.md)
             .when(self.full_width, |this| this.w_full())
             .when_some(
                 self.on_click.filter(|_| !self.disabled),
                 |this, on_click| this.on_click(on_click),
             )
             .shadow_2xs()
-            .text_sm()
+            .text_size(theme.text.sm)
             .text_color(match self.variant {
-                ButtonVariant::Solid => rgb(0xffffff).into(),
+                ButtonVariant::Solid => theme.primary_foreground,
                 _ => theme.foreground,
             })
             .border_1()
@@ -111,6 +118,7 @@ impl RenderOnce for Button {
                 })
             })
             .border_color(match self.variant {
+                _ if self.focused => theme.focus_ring,
                 ButtonVariant::Ghost => theme.transparent,
                 ButtonVariant::Outlined => theme.border,
                 ButtonVariant::Solid => theme.primary_hover,
@@ -131,7 +139,10 @@ impl RenderOnce for Button {
         if let Some(icon) = self.icon_path {
             root = root
                 .justify_between()
-                .child(svg().path(icon).size_3().text_color(rgb(0x555555)))
+                .child(svg().path(icon).size_3().text_color(match self.variant {
+                    ButtonVariant::Solid => theme.primary_foreground,
+                    _ => theme.muted_foreground,
+                }))
         }
 
         root
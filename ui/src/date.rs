@@ -0,0 +1,253 @@
+use gpui::SharedString;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const FORMAT_ERROR: &str = "Expected a date like DD.MM.YYYY";
+
+/// A day in the proleptic Gregorian calendar, written as `DD.MM.YYYY`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        ((1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day))
+            .then_some(Self { year, month, day })
+    }
+
+    /// The current date in the local timezone. Where the timezone can't be
+    /// read, which is anywhere but Unix, it is the date in UTC.
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs() as i64);
+        Self::from_days((seconds + utc_offset(seconds)).div_euclid(86_400))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, SharedString> {
+        let parts: Vec<&str> = text.split('.').collect();
+        let [day, month, year] = parts[..] else {
+            return Err(FORMAT_ERROR.into());
+        };
+        if year.len() != 4 {
+            return Err(FORMAT_ERROR.into());
+        }
+
+        let (Ok(day), Ok(month), Ok(year)) = (
+            day.parse::<u32>(),
+            month.parse::<u32>(),
+            year.parse::<i32>(),
+        ) else {
+            return Err(FORMAT_ERROR.into());
+        };
+
+        if !(1..=12).contains(&month) {
+            return Err("Month must be between 1 and 12".into());
+        }
+        let max_day = days_in_month(year, month);
+        Self::new(year, month, day)
+            .ok_or_else(|| format!("Day must be between 1 and {max_day}").into())
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Days since Monday, so Monday is `0` and Sunday is `6`.
+    pub fn weekday(&self) -> u32 {
+        (self.to_days() + 3).rem_euclid(7) as u32
+    }
+
+    pub fn first_of_month(&self) -> Self {
+        Self { day: 1, ..*self }
+    }
+
+    pub fn add_days(&self, days: i64) -> Self {
+        Self::from_days(self.to_days() + days)
+    }
+
+    /// Moves by whole months, keeping the day where the target month allows
+    /// it and using its last day otherwise.
+    pub fn add_months(&self, months: i32) -> Self {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        let year = index.div_euclid(12);
+        let month = index.rem_euclid(12) as u32 + 1;
+        Self {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    // Days since 1970-01-01, after Howard Hinnant's `days_from_civil`.
+    fn to_days(self) -> i64 {
+        let year = self.year as i64 - (self.month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = (year_of_era + era * 400) as i32 + (month <= 2) as i32;
+        Self { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}.{:02}.{:04}", self.day, self.month, self.year)
+    }
+}
+
+/// Seconds the local timezone was ahead of UTC at `seconds` past the epoch.
+#[cfg(unix)]
+fn utc_offset(seconds: i64) -> i64 {
+    let time = seconds as libc::time_t;
+    // SAFETY: `tm` is plain data that `localtime_r` fills in, and both
+    // pointers are valid for the duration of the call.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+        return 0;
+    }
+    tm.tm_gmtoff as i64
+}
+
+#[cfg(not(unix))]
+fn utc_offset(_seconds: i64) -> i64 {
+    0
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        _ if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        _ => 28,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn new_rejects_out_of_range_parts() {
+        assert_eq!(Date::new(2025, 0, 1), None);
+        assert_eq!(Date::new(2025, 13, 1), None);
+        assert_eq!(Date::new(2025, 4, 31), None);
+        assert_eq!(Date::new(2025, 2, 29), None);
+        assert!(Date::new(2024, 2, 29).is_some());
+    }
+
+    #[test]
+    fn days_in_month_follows_leap_years() {
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2023, 2), 28);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(2025, 9), 30);
+    }
+
+    #[test]
+    fn parse_accepts_valid_dates() {
+        assert_eq!(Date::parse("03.02.2025"), Ok(date(2025, 2, 3)));
+        assert_eq!(Date::parse("3.2.2025"), Ok(date(2025, 2, 3)));
+        assert_eq!(Date::parse("29.02.2024"), Ok(date(2024, 2, 29)));
+    }
+
+    #[test]
+    fn parse_explains_what_is_wrong() {
+        for text in [
+            "",
+            "03.02",
+            "03.02.25",
+            "aa.bb.cccc",
+            "03.02.2025.1",
+            "03-02-2025",
+        ] {
+            assert_eq!(Date::parse(text), Err(FORMAT_ERROR.into()), "{text}");
+        }
+        assert_eq!(
+            Date::parse("01.13.2025"),
+            Err("Month must be between 1 and 12".into())
+        );
+        assert_eq!(
+            Date::parse("31.04.2025"),
+            Err("Day must be between 1 and 30".into())
+        );
+        assert_eq!(
+            Date::parse("29.02.2023"),
+            Err("Day must be between 1 and 28".into())
+        );
+    }
+
+    #[test]
+    fn display_round_trips_through_parse() {
+        let date = date(987, 12, 5);
+        assert_eq!(date.to_string(), "05.12.0987");
+        assert_eq!(Date::parse(&date.to_string()), Ok(date));
+    }
+
+    #[test]
+    fn day_numbers_round_trip() {
+        assert_eq!(Date::from_days(0), date(1970, 1, 1));
+        for days in (-800_000..800_000).step_by(997) {
+            assert_eq!(Date::from_days(days).to_days(), days);
+        }
+    }
+
+    #[test]
+    fn weekday_starts_on_monday() {
+        assert_eq!(date(1970, 1, 1).weekday(), 3);
+        assert_eq!(date(2025, 1, 1).weekday(), 2);
+        assert_eq!(date(2024, 12, 29).weekday(), 6);
+    }
+
+    #[test]
+    fn add_days_crosses_months_and_years() {
+        assert_eq!(date(2024, 12, 31).add_days(1), date(2025, 1, 1));
+        assert_eq!(date(2024, 3, 1).add_days(-1), date(2024, 2, 29));
+        assert_eq!(date(2025, 1, 1).add_days(365), date(2026, 1, 1));
+    }
+
+    #[test]
+    fn add_months_clamps_the_day() {
+        assert_eq!(date(2025, 1, 31).add_months(1), date(2025, 2, 28));
+        assert_eq!(date(2024, 1, 31).add_months(1), date(2024, 2, 29));
+        assert_eq!(date(2025, 3, 31).add_months(-1), date(2025, 2, 28));
+        assert_eq!(date(2025, 11, 15).add_months(3), date(2026, 2, 15));
+        assert_eq!(date(2025, 1, 15).add_months(-1), date(2024, 12, 15));
+    }
+}
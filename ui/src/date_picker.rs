@@ -0,0 +1,524 @@
+use crate::date::Date;
+use crate::mask::InputMask;
+use crate::popover::Popover;
+use crate::text_input::{TextInput, TextInputEvent};
+use crate::theme::ThemeAble;
+use crate::validator::Validator;
+use crate::{Button, ButtonVariant};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString,
+    Subscription, Window, actions, div, prelude::*,
+};
+use std::cell::RefCell;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+// What the field shows while empty; a complete date is as long as this.
+const DATE_FORMAT: &str = "DD.MM.YYYY";
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+actions!(
+    date_picker,
+    [
+        OpenCalendar,
+        CloseCalendar,
+        PreviousDay,
+        NextDay,
+        PreviousWeek,
+        NextWeek,
+        PreviousMonth,
+        NextMonth,
+        SelectDate,
+    ]
+);
+
+pub enum DatePickerEvent {
+    Changed(Option<Date>),
+}
+
+type DisabledDates = Box<dyn Fn(Date) -> bool>;
+
+#[derive(Default)]
+struct Constraints {
+    min: Option<Date>,
+    max: Option<Date>,
+    disabled: Option<DisabledDates>,
+}
+
+impl Constraints {
+    fn check(&self, date: Date) -> Result<(), SharedString> {
+        if let Some(min) = self.min.filter(|min| date < *min) {
+            return Err(format!("Date must be on or after {min}").into());
+        }
+        if let Some(max) = self.max.filter(|max| date > *max) {
+            return Err(format!("Date must be on or before {max}").into());
+        }
+        if self
+            .disabled
+            .as_ref()
+            .is_some_and(|disabled| disabled(date))
+        {
+            return Err("This date is not available".into());
+        }
+        Ok(())
+    }
+
+    fn clamp(&self, date: Date) -> Date {
+        let date = self.min.map_or(date, |min| date.max(min));
+        self.max.map_or(date, |max| date.min(max))
+    }
+}
+
+struct DateValidator(Rc<RefCell<Constraints>>);
+
+impl Validator for DateValidator {
+    fn allows(&self, ch: char) -> bool {
+        ch.is_ascii_digit() || ch == '.'
+    }
+
+    fn validate(&self, content: &str) -> Result<(), SharedString> {
+        if content.is_empty() {
+            return Ok(());
+        }
+        self.0.borrow().check(Date::parse(content)?)
+    }
+
+    fn is_pending(&self, content: &str) -> bool {
+        content.len() < DATE_FORMAT.len()
+    }
+}
+
+/// A `DD.MM.YYYY` text field with a month calendar in a [`Popover`].
+pub struct DatePicker {
+    input: Entity<TextInput>,
+    calendar_focus: FocusHandle,
+    constraints: Rc<RefCell<Constraints>>,
+    date: Option<Date>,
+    cursor: Date,
+    highlight: Option<RangeInclusive<Date>>,
+    open: bool,
+    disabled: bool,
+    _subscription: Subscription,
+}
+
+impl DatePicker {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let constraints = Rc::new(RefCell::new(Constraints::default()));
+        let validator = DateValidator(constraints.clone());
+        let input = cx.new(|cx| {
+            TextInput::new(window, cx)
+                .mask(InputMask::new("99.99.9999").ghost(DATE_FORMAT))
+                .validator(validator)
+        });
+        let subscription = cx.subscribe(&input, |this, _, event, cx| {
+            if let TextInputEvent::Changed { new, .. } = event {
+                this.update_date(this.parse(new), cx);
+            }
+        });
+
+        Self {
+            input,
+            calendar_focus: cx.focus_handle(),
+            constraints,
+            date: None,
+            cursor: Date::today(),
+            highlight: None,
+            open: false,
+            disabled: false,
+            _subscription: subscription,
+        }
+    }
+
+    pub fn min(self, min: Date) -> Self {
+        self.constraints.borrow_mut().min = Some(min);
+        self
+    }
+
+    pub fn max(self, max: Date) -> Self {
+        self.constraints.borrow_mut().max = Some(max);
+        self
+    }
+
+    /// Dates for which `disabled` returns `true` can't be picked or typed.
+    pub fn disabled_dates(self, disabled: impl Fn(Date) -> bool + 'static) -> Self {
+        self.constraints.borrow_mut().disabled = Some(Box::new(disabled));
+        self
+    }
+
+    pub fn date(&self) -> Option<Date> {
+        self.date
+    }
+
+    /// Replaces the date without emitting [`DatePickerEvent::Changed`].
+    pub fn set_date(&mut self, date: Option<Date>, cx: &mut Context<Self>) {
+        self.date = date;
+        self.set_text(date.map(|date| date.to_string()).unwrap_or_default(), cx);
+        cx.notify();
+    }
+
+    pub fn set_min(&mut self, min: Option<Date>, cx: &mut Context<Self>) {
+        self.constraints.borrow_mut().min = min;
+        self.revalidate(cx);
+    }
+
+    pub fn set_max(&mut self, max: Option<Date>, cx: &mut Context<Self>) {
+        self.constraints.borrow_mut().max = max;
+        self.revalidate(cx);
+    }
+
+    /// Shades the days in `highlight`, e.g. the span between an outbound and a
+    /// return date.
+    pub fn set_highlight(
+        &mut self,
+        highlight: Option<RangeInclusive<Date>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.highlight = highlight;
+        cx.notify();
+    }
+
+    pub fn disabled(&mut self, disabled: bool, cx: &mut Context<Self>) {
+        if self.disabled == disabled {
+            return;
+        }
+        self.disabled = disabled;
+        self.open &= !disabled;
+        self.input.update(cx, |input, _| input.disabled(disabled));
+        cx.notify();
+    }
+
+    fn parse(&self, text: &str) -> Option<Date> {
+        Date::parse(text)
+            .ok()
+            .filter(|date| self.is_selectable(*date))
+    }
+
+    fn is_selectable(&self, date: Date) -> bool {
+        self.constraints.borrow().check(date).is_ok()
+    }
+
+    fn revalidate(&mut self, cx: &mut Context<Self>) {
+        let text = self.input.read(cx).content.clone();
+        self.update_date(self.parse(&text), cx);
+        cx.notify();
+    }
+
+    fn update_date(&mut self, date: Option<Date>, cx: &mut Context<Self>) {
+        if let Some(date) = date {
+            self.cursor = date;
+        }
+        if self.date != date {
+            self.date = date;
+            cx.emit(DatePickerEvent::Changed(date));
+            cx.notify();
+        }
+    }
+
+    fn set_text(&self, text: String, cx: &mut Context<Self>) {
+        self.input.update(cx, |input, cx| {
+            let end = text.len();
+            input.set_content(text, cx);
+            input.selected_range = end..end;
+            cx.notify();
+        });
+    }
+
+    fn select(&mut self, date: Date, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.is_selectable(date) {
+            return;
+        }
+        self.set_text(date.to_string(), cx);
+        self.update_date(Some(date), cx);
+        self.close_calendar(&CloseCalendar, window, cx);
+    }
+
+    fn open_calendar(&mut self, _: &OpenCalendar, window: &mut Window, cx: &mut Context<Self>) {
+        if self.disabled {
+            return;
+        }
+        let today = self.constraints.borrow().clamp(Date::today());
+        self.cursor = self.date.unwrap_or(today);
+        self.open = true;
+        window.focus(&self.calendar_focus);
+        cx.notify();
+    }
+
+    fn close_calendar(&mut self, _: &CloseCalendar, window: &mut Window, cx: &mut Context<Self>) {
+        self.open = false;
+        window.focus(&self.input.focus_handle(cx));
+        cx.notify();
+    }
+
+    fn move_cursor(&mut self, cursor: Date, cx: &mut Context<Self>) {
+        self.cursor = self.constraints.borrow().clamp(cursor);
+        cx.notify();
+    }
+
+    fn previous_day(&mut self, _: &PreviousDay, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.cursor.add_days(-1), cx);
+    }
+
+    fn next_day(&mut self, _: &NextDay, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.cursor.add_days(1), cx);
+    }
+
+    fn previous_week(&mut self, _: &PreviousWeek, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.cursor.add_days(-7), cx);
+    }
+
+    fn next_week(&mut self, _: &NextWeek, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.cursor.add_days(7), cx);
+    }
+
+    fn previous_month(&mut self, _: &PreviousMonth, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.cursor.add_months(-1), cx);
+    }
+
+    fn next_month(&mut self, _: &NextMonth, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_cursor(self.cursor.add_months(1), cx);
+    }
+
+    fn select_date(&mut self, _: &SelectDate, window: &mut Window, cx: &mut Context<Self>) {
+        self.select(self.cursor, window, cx);
+    }
+
+    fn render_calendar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let first = self.cursor.first_of_month();
+        let start = first.add_days(-(first.weekday() as i64));
+        let today = Date::today();
+
+        let header = div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .child(
+                div()
+                    .id("previous-month")
+                    .px(theme.spacing.md)
+                    .rounded(theme.radius.md)
+                    .hover(|this| this.bg(theme.highlight))
+                    .child("‹")
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.previous_month(&PreviousMonth, window, cx)
+                    })),
+            )
+            .child(format!(
+                "{} {}",
+                MONTHS[first.month() as usize - 1],
+                first.year()
+            ))
+            .child(
+                div()
+                    .id("next-month")
+                    .px(theme.spacing.md)
+                    .rounded(theme.radius.md)
+                    .hover(|this| this.bg(theme.highlight))
+                    .child("›")
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.next_month(&NextMonth, window, cx)),
+                    ),
+            );
+
+        let weekdays = div().flex().children(WEEKDAYS.iter().map(|weekday| {
+            div()
+                .size_7()
+                .flex()
+                .items_center()
+                .justify_center()
+                .text_size(theme.text.xs)
+                .opacity(0.6)
+                .child(*weekday)
+        }));
+
+        let weeks = (0..6usize).map(|week| {
+            div().flex().children((0..7usize).map(|weekday| {
+                let ix = week * 7 + weekday;
+                let date = start.add_days(ix as i64);
+                let selectable = self.is_selectable(date);
+                let selected = self.date == Some(date);
+                let highlighted = self
+                    .highlight
+                    .as_ref()
+                    .is_some_and(|range| range.contains(&date));
+
+                div()
+                    .id(("day", ix))
+                    .size_7()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .rounded(theme.radius.md)
+                    .border_1()
+                    .border_color(if date == self.cursor {
+                        theme.primary
+                    } else {
+                        theme.transparent
+                    })
+                    .when(date.month() != first.month(), |this| this.opacity(0.5))
+                    .when(date == today, |this| {
+                        this.font_weight(gpui::FontWeight::BOLD)
+                    })
+                    .when(highlighted && !selected, |this| this.bg(theme.highlight))
+                    .when(selected, |this| {
+                        this.bg(theme.primary).text_color(theme.primary_foreground)
+                    })
+                    .when(!selectable, |this| this.opacity(0.25).line_through())
+                    .when(selectable, |this| {
+                        this.when(!selected, |this| {
+                            this.hover(|this| this.bg(theme.highlight))
+                        })
+                        .on_click(
+                            cx.listener(move |this, _, window, cx| this.select(date, window, cx)),
+                        )
+                    })
+                    .child(date.day().to_string())
+            }))
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(theme.spacing.sm)
+            .p(theme.spacing.sm)
+            .text_size(theme.text.sm)
+            .key_context("Calendar")
+            .track_focus(&self.calendar_focus)
+            .on_action(cx.listener(Self::close_calendar))
+            .on_action(cx.listener(Self::previous_day))
+            .on_action(cx.listener(Self::next_day))
+            .on_action(cx.listener(Self::previous_week))
+            .on_action(cx.listener(Self::next_week))
+            .on_action(cx.listener(Self::previous_month))
+            .on_action(cx.listener(Self::next_month))
+            .on_action(cx.listener(Self::select_date))
+            .child(header)
+            .child(weekdays)
+            .children(weeks)
+    }
+}
+
+impl Render for DatePicker {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let trigger = div()
+            .flex()
+            .items_start()
+            .gap(cx.theme().spacing.sm)
+            .w_full()
+            .child(self.input.clone())
+            .child(
+                Button::new(format!("date-picker-toggle-{}", cx.entity_id()).into())
+                    .variant(ButtonVariant::Ghost)
+                    .with_label("▾".into())
+                    .disabled(self.disabled)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        // The popover ignores mouse-downs on its trigger, so
+                        // the button itself closes an open calendar.
+                        if this.open {
+                            this.close_calendar(&CloseCalendar, window, cx)
+                        } else {
+                            this.open_calendar(&OpenCalendar, window, cx)
+                        }
+                    })),
+            );
+
+        div()
+            .w_full()
+            .key_context("DatePicker")
+            .on_action(cx.listener(Self::open_calendar))
+            .child(
+                Popover::new(
+                    ("date-picker", cx.entity_id()).into(),
+                    trigger.into_any_element(),
+                    self.render_calendar(cx).into_any_element(),
+                )
+                .open(self.open)
+                .on_dismiss(cx.listener(|this, _: &DismissEvent, _, cx| {
+                    this.open = false;
+                    cx.notify();
+                })),
+            )
+    }
+}
+
+impl EventEmitter<DatePickerEvent> for DatePicker {}
+
+impl Focusable for DatePicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.read(cx).focus_handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    fn constraints() -> Constraints {
+        Constraints {
+            min: Some(date(2025, 3, 1)),
+            max: Some(date(2025, 3, 31)),
+            disabled: Some(Box::new(|date| date.weekday() >= 5)),
+        }
+    }
+
+    #[test]
+    fn clamp_keeps_dates_within_min_and_max() {
+        let constraints = constraints();
+        assert_eq!(constraints.clamp(date(2025, 2, 14)), date(2025, 3, 1));
+        assert_eq!(constraints.clamp(date(2025, 3, 14)), date(2025, 3, 14));
+        assert_eq!(constraints.clamp(date(2025, 4, 14)), date(2025, 3, 31));
+        assert_eq!(
+            Constraints::default().clamp(date(1999, 1, 1)),
+            date(1999, 1, 1)
+        );
+    }
+
+    #[test]
+    fn check_reports_the_violated_constraint() {
+        let constraints = constraints();
+        assert_eq!(constraints.check(date(2025, 3, 3)), Ok(()));
+        assert_eq!(
+            constraints.check(date(2025, 2, 28)),
+            Err("Date must be on or after 01.03.2025".into())
+        );
+        assert_eq!(
+            constraints.check(date(2025, 4, 1)),
+            Err("Date must be on or before 31.03.2025".into())
+        );
+        assert_eq!(
+            constraints.check(date(2025, 3, 1)),
+            Err("This date is not available".into())
+        );
+    }
+
+    #[test]
+    fn validator_accepts_empty_and_rejects_malformed_input() {
+        let validator = DateValidator(Rc::new(RefCell::new(constraints())));
+        assert!(validator.allows('7') && validator.allows('.'));
+        assert!(!validator.allows('x'));
+        assert_eq!(validator.validate(""), Ok(()));
+        assert_eq!(validator.validate("03.03.2025"), Ok(()));
+        assert!(validator.validate("03.03").is_err());
+        assert!(validator.is_pending("03.03"));
+        assert!(!validator.is_pending("03.03.2025"));
+    }
+}
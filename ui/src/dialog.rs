@@ -0,0 +1,275 @@
+use crate::button::{Button, ButtonVariant};
+use crate::theme::ThemeAble;
+use futures::channel::oneshot;
+use gpui::{
+    Context, EventEmitter, FocusHandle, Focusable, FontWeight, SharedString, Subscription, Window,
+    actions, anchored, deferred, div, point, prelude::*, px,
+};
+
+const PRIORITY: usize = 20;
+
+actions!(
+    dialog,
+    [Cancel, ConfirmAction, FocusNextAction, FocusPreviousAction]
+);
+
+/// Emitted when the dialog closes, with the index of the chosen action or
+/// `None` if it was cancelled.
+pub enum DialogEvent {
+    Closed(Option<usize>),
+}
+
+/// A modal dialog drawn over the whole window.
+///
+/// While it is open the rest of the window doesn't receive mouse input and
+/// keyboard focus stays inside the dialog. Tab and the arrow keys move between
+/// the actions, Enter or Space chooses the focused one and Escape cancels.
+/// Render the entity anywhere in the window; it draws nothing while closed.
+pub struct Dialog {
+    focus_handle: FocusHandle,
+    title: SharedString,
+    body: Option<SharedString>,
+    actions: Vec<(SharedString, ButtonVariant)>,
+    default_action: usize,
+    focused_action: usize,
+    open: bool,
+    previous_focus: Option<FocusHandle>,
+    responders: Vec<oneshot::Sender<Option<usize>>>,
+    focus_trap: Option<Subscription>,
+}
+
+impl Dialog {
+    pub fn new(title: SharedString, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            title,
+            body: None,
+            actions: Vec::new(),
+            default_action: 0,
+            focused_action: 0,
+            open: false,
+            previous_focus: None,
+            responders: Vec::new(),
+            focus_trap: None,
+        }
+    }
+
+    pub fn body(mut self, body: SharedString) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Adds a button to the row of actions. Its index, counting from zero in
+    /// the order the actions were added, is what the dialog responds with.
+    pub fn action(mut self, label: SharedString, variant: ButtonVariant) -> Self {
+        self.actions.push((label, variant));
+        self
+    }
+
+    /// The action that has focus when the dialog opens. Defaults to the first.
+    pub fn default_action(mut self, ix: usize) -> Self {
+        self.default_action = ix;
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Shows the dialog and moves focus into it. The returned receiver gets
+    /// the same response as [`DialogEvent::Closed`].
+    pub fn open(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<Option<usize>> {
+        let (sender, receiver) = oneshot::channel();
+        self.responders.push(sender);
+        if self.open {
+            return receiver;
+        }
+
+        self.open = true;
+        self.focused_action = self.default_action;
+        self.previous_focus = window.focused(cx);
+        window.focus(&self.focus_handle);
+        self.focus_trap =
+            Some(
+                cx.on_focus_out(&self.focus_handle, window, |this, _, window, _| {
+                    if this.open {
+                        window.focus(&this.focus_handle);
+                    }
+                }),
+            );
+        cx.notify();
+        receiver
+    }
+
+    /// Closes the dialog with `response`, as if the action at that index had
+    /// been chosen, or cancelled for `None`. Focus goes back to where it was
+    /// before the dialog opened.
+    pub fn respond(
+        &mut self,
+        response: Option<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.open {
+            return;
+        }
+        self.open = false;
+        self.focus_trap = None;
+        if let Some(previous_focus) = self.previous_focus.take() {
+            window.focus(&previous_focus);
+        }
+        for responder in self.responders.drain(..) {
+            responder.send(response).ok();
+        }
+        cx.emit(DialogEvent::Closed(response));
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.respond(None, window, cx);
+    }
+
+    fn confirm(&mut self, _: &ConfirmAction, window: &mut Window, cx: &mut Context<Self>) {
+        if self.focused_action < self.actions.len() {
+            self.respond(Some(self.focused_action), window, cx);
+        }
+    }
+
+    fn focus_next_action(&mut self, _: &FocusNextAction, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.actions.is_empty() {
+            self.focused_action = (self.focused_action + 1) % self.actions.len();
+            cx.notify();
+        }
+    }
+
+    fn focus_previous_action(
+        &mut self,
+        _: &FocusPreviousAction,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.actions.is_empty() {
+            self.focused_action =
+                (self.focused_action + self.actions.len() - 1) % self.actions.len();
+            cx.notify();
+        }
+    }
+}
+
+impl Render for Dialog {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !self.open {
+            return div().into_any_element();
+        }
+
+        let theme = cx.theme();
+        let viewport = window.viewport_size();
+
+        let actions = self
+            .actions
+            .iter()
+            .enumerate()
+            .map(|(ix, (label, variant))| {
+                Button::new(format!("dialog-{}-action-{ix}", cx.entity_id()).into())
+                    .variant(*variant)
+                    .with_label(label.clone())
+                    .focused(ix == self.focused_action)
+                    .on_click(
+                        cx.listener(move |this, _, window, cx| this.respond(Some(ix), window, cx)),
+                    )
+            });
+
+        let panel = div()
+            .flex()
+            .flex_col()
+            .gap(theme.spacing.lg)
+            .max_w(px(360.))
+            .m(theme.spacing.xl)
+            .p(theme.spacing.xl)
+            .border_1()
+            .border_color(theme.border)
+            .rounded(theme.radius.lg)
+            .shadow_lg()
+            .bg(theme.surface)
+            .text_color(theme.foreground)
+            .child(
+                div()
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .child(self.title.clone()),
+            )
+            .when_some(self.body.clone(), |this, body| {
+                this.child(div().text_size(theme.text.sm).child(body))
+            })
+            .child(
+                div()
+                    .flex()
+                    .justify_end()
+                    .gap(theme.spacing.md)
+                    .children(actions),
+            );
+
+        deferred(
+            anchored().position(point(px(0.), px(0.))).child(
+                div()
+                    .id(("dialog", cx.entity_id()))
+                    .w(viewport.width)
+                    .h(viewport.height)
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .occlude()
+                    .bg(theme.overlay)
+                    .key_context("Dialog")
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::cancel))
+                    .on_action(cx.listener(Self::confirm))
+                    .on_action(cx.listener(Self::focus_next_action))
+                    .on_action(cx.listener(Self::focus_previous_action))
+                    .child(panel),
+            ),
+        )
+        .with_priority(PRIORITY)
+        .into_any_element()
+    }
+}
+
+impl EventEmitter<DialogEvent> for Dialog {}
+
+impl Focusable for Dialog {
+    fn focus_handle(&self, _: &gpui::App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    fn responds_to_the_keyboard(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let (dialog, cx) = cx.add_window_view(|_, cx| {
+            Dialog::new("Discard changes?".into(), cx)
+                .action("Keep".into(), ButtonVariant::Outlined)
+                .action("Discard".into(), ButtonVariant::Solid)
+        });
+
+        let mut response = dialog.update_in(cx, |dialog, window, cx| dialog.open(window, cx));
+        cx.simulate_keystrokes("escape");
+        assert_eq!(response.try_recv(), Ok(Some(None)));
+        assert!(!dialog.read_with(cx, |dialog, _| dialog.is_open()));
+
+        let mut response = dialog.update_in(cx, |dialog, window, cx| dialog.open(window, cx));
+        cx.simulate_keystrokes("enter");
+        assert_eq!(response.try_recv(), Ok(Some(Some(0))));
+
+        let mut response = dialog.update_in(cx, |dialog, window, cx| dialog.open(window, cx));
+        cx.simulate_keystrokes("tab enter");
+        assert_eq!(response.try_recv(), Ok(Some(Some(1))));
+    }
+}
@@ -1,15 +1,30 @@
 use gpui::{
-    AnyElement, App, ClickEvent, ElementId, InteractiveElement, IntoElement, ParentElement,
-    RenderOnce, SharedString, StatefulInteractiveElement, Styled, Window, div, rgb,
+    AnyElement, App, ClickEvent, CursorStyle, DismissEvent, ElementId, Entity, FocusHandle,
+    InteractiveElement, IntoElement, KeyDownEvent, MouseButton, ParentElement, RenderOnce,
+    SharedString, StatefulInteractiveElement, Styled, Window, actions, div, prelude::*,
 };
+use std::rc::Rc;
 
 use crate::Popover;
+use crate::button::ClickHandler;
+use crate::theme::ThemeAble;
+
+actions!(
+    dropdown,
+    [SelectPrevious, SelectNext, SelectFirst, SelectLast, Confirm]
+);
+
+type SelectHandler = Rc<dyn Fn(&usize, &mut Window, &mut App)>;
+type DismissHandler = Rc<dyn Fn(&DismissEvent, &mut Window, &mut App)>;
 
 #[derive(IntoElement)]
 pub struct MenuItem {
     id: ElementId,
     label: SharedString,
-    on_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
+    on_click: Option<ClickHandler>,
+    highlighted: bool,
+    selected: bool,
+    disabled: bool,
 }
 
 impl MenuItem {
@@ -18,9 +33,17 @@ impl MenuItem {
             id,
             label,
             on_click: None,
+            highlighted: false,
+            selected: false,
+            disabled: false,
         }
     }
 
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
     pub fn on_click(
         mut self,
         listener: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
@@ -31,17 +54,25 @@ impl MenuItem {
 }
 
 impl RenderOnce for MenuItem {
-    fn render(self, _window: &mut gpui::Window, _cx: &mut gpui::App) -> impl IntoElement {
+    fn render(self, _window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+        let theme = cx.theme();
         let mut root = div()
             .id(self.id)
-            .rounded_md()
-            .px_2()
-            .py_0p5()
-            .text_sm()
-            .hover(|this| this.bg(rgb(0xfafafa)))
+            .rounded(theme.radius.md)
+            .px(theme.spacing.md)
+            .py(theme.spacing.xs)
+            .text_size(theme.text.sm)
+            .when(!self.disabled, |this| {
+                this.hover(|this| this.bg(theme.highlight))
+            })
+            .when(self.highlighted, |this| this.bg(theme.highlight))
+            .when(self.selected, |this| this.text_color(theme.primary))
+            .when(self.disabled, |this| {
+                this.cursor(CursorStyle::OperationNotAllowed).opacity(0.5)
+            })
             .child(self.label);
 
-        if let Some(click_handler) = self.on_click {
+        if let Some(click_handler) = self.on_click.filter(|_| !self.disabled) {
             root = root.on_click(click_handler);
         }
 
@@ -49,13 +80,48 @@ impl RenderOnce for MenuItem {
     }
 }
 
+struct DropdownState {
+    focus_handle: FocusHandle,
+    highlighted: Option<usize>,
+    open: bool,
+    was_open: bool,
+}
+
+fn highlight(state: &Entity<DropdownState>, ix: usize, cx: &mut App) {
+    state.update(cx, |state, cx| {
+        if state.highlighted != Some(ix) {
+            state.highlighted = Some(ix);
+            cx.notify();
+        }
+    });
+}
+
+fn first_enabled(disabled: &[bool], mut candidates: impl Iterator<Item = usize>) -> Option<usize> {
+    candidates.find(|ix| !disabled[*ix])
+}
+
+fn set_open(state: &Entity<DropdownState>, open: bool, cx: &mut App) {
+    state.update(cx, |state, cx| {
+        state.open = open;
+        cx.notify();
+    });
+}
+
+/// A menu of [`MenuItem`]s in a [`Popover`]. While it is open the menu has
+/// focus and can be driven from the keyboard.
+///
+/// Like [`Popover`], a dropdown from [`Dropdown::new`] is opened by its owner
+/// and one from [`Dropdown::stateful`] opens on a press of the trigger and
+/// closes by itself once an item is chosen or it is dismissed.
 #[derive(IntoElement)]
 pub struct Dropdown {
     id: ElementId,
     trigger: AnyElement,
     menu: Vec<MenuItem>,
     selected: Option<usize>,
-    open: bool,
+    open: Option<bool>,
+    on_select: Option<SelectHandler>,
+    on_dismiss: Option<DismissHandler>,
 }
 
 impl Dropdown {
@@ -65,24 +131,217 @@ impl Dropdown {
             trigger,
             menu,
             selected: None,
-            open: false,
+            open: Some(false),
+            on_select: None,
+            on_dismiss: None,
+        }
+    }
+
+    pub fn stateful(id: ElementId, trigger: AnyElement, menu: Vec<MenuItem>) -> Self {
+        Self {
+            open: None,
+            ..Self::new(id, trigger, menu)
         }
     }
 
     pub fn open(mut self, open: bool) -> Self {
-        self.open = open;
+        self.open = Some(open);
+        self
+    }
+
+    /// Marks the item at `selected` as the current choice. It is highlighted
+    /// first whenever the menu opens.
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Called with the item's index when it is clicked or confirmed with
+    /// Enter or Space.
+    pub fn on_select(mut self, listener: impl Fn(&usize, &mut Window, &mut App) + 'static) -> Self {
+        self.on_select = Some(Rc::new(listener));
         self
     }
 
-    pub fn selected(self) -> Option<usize> {
-        self.selected
+    /// Called when the menu is dismissed without choosing an item.
+    pub fn on_dismiss(
+        mut self,
+        listener: impl Fn(&DismissEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_dismiss = Some(Rc::new(listener));
+        self
     }
 }
 
 impl RenderOnce for Dropdown {
-    fn render(self, _window: &mut gpui::Window, _cx: &mut gpui::App) -> impl IntoElement {
-        let menu = div().children(self.menu);
+    fn render(self, window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, cx| DropdownState {
+            focus_handle: cx.focus_handle(),
+            highlighted: None,
+            open: false,
+            was_open: false,
+        });
+
+        let controlled = self.open.is_some();
+        let open = self.open.unwrap_or_else(|| state.read(cx).open);
+        let item_count = self.menu.len();
+        let disabled: Rc<[bool]> = self.menu.iter().map(|item| item.disabled).collect();
+        let opened = open && !state.read(cx).was_open;
+        state.update(cx, |state, _| {
+            state.was_open = open;
+            if opened {
+                state.highlighted = self
+                    .selected
+                    .or_else(|| first_enabled(&disabled, 0..item_count));
+            }
+        });
+        let focus_handle = state.read(cx).focus_handle.clone();
+        if opened {
+            window.focus(&focus_handle);
+        }
+
+        let select: SelectHandler = Rc::new({
+            let state = state.clone();
+            let on_select = self.on_select.clone();
+            move |ix, window, cx| {
+                if !controlled {
+                    set_open(&state, false, cx);
+                }
+                if let Some(on_select) = &on_select {
+                    on_select(ix, window, cx);
+                }
+            }
+        });
+        let dismiss = {
+            let state = state.clone();
+            let on_dismiss = self.on_dismiss.clone();
+            move |event: &DismissEvent, window: &mut Window, cx: &mut App| {
+                if !controlled {
+                    set_open(&state, false, cx);
+                }
+                if let Some(on_dismiss) = &on_dismiss {
+                    on_dismiss(event, window, cx);
+                }
+            }
+        };
+
+        let highlighted = state.read(cx).highlighted;
+        let labels: Vec<SharedString> = self.menu.iter().map(|item| item.label.clone()).collect();
+
+        let items: Vec<_> = self
+            .menu
+            .into_iter()
+            .enumerate()
+            .map(|(ix, mut item)| {
+                item.highlighted = highlighted == Some(ix);
+                item.selected = self.selected == Some(ix);
+                let select = select.clone();
+                let on_click = item.on_click.take();
+                item.on_click = Some(Box::new(move |event, window, cx| {
+                    if let Some(on_click) = &on_click {
+                        on_click(event, window, cx);
+                    }
+                    select(&ix, window, cx);
+                }));
+
+                let state = state.clone();
+                div()
+                    .when(!item.disabled, |this| {
+                        this.on_mouse_move(move |_, _, cx| highlight(&state, ix, cx))
+                    })
+                    .child(item)
+            })
+            .collect();
+
+        let menu = div()
+            .track_focus(&focus_handle)
+            .key_context("Dropdown")
+            .on_action({
+                let (state, disabled) = (state.clone(), disabled.clone());
+                move |_: &SelectPrevious, _, cx| {
+                    let end = highlighted.unwrap_or(item_count);
+                    if let Some(ix) = first_enabled(&disabled, (0..end).rev()) {
+                        highlight(&state, ix, cx);
+                    }
+                }
+            })
+            .on_action({
+                let (state, disabled) = (state.clone(), disabled.clone());
+                move |_: &SelectNext, _, cx| {
+                    let start = highlighted.map_or(0, |ix| ix + 1);
+                    if let Some(ix) = first_enabled(&disabled, start..item_count) {
+                        highlight(&state, ix, cx);
+                    }
+                }
+            })
+            .on_action({
+                let (state, disabled) = (state.clone(), disabled.clone());
+                move |_: &SelectFirst, _, cx| {
+                    if let Some(ix) = first_enabled(&disabled, 0..item_count) {
+                        highlight(&state, ix, cx);
+                    }
+                }
+            })
+            .on_action({
+                let (state, disabled) = (state.clone(), disabled.clone());
+                move |_: &SelectLast, _, cx| {
+                    if let Some(ix) = first_enabled(&disabled, (0..item_count).rev()) {
+                        highlight(&state, ix, cx);
+                    }
+                }
+            })
+            .on_action({
+                let disabled = disabled.clone();
+                move |_: &Confirm, window, cx| {
+                    if let Some(ix) = highlighted.filter(|ix| !disabled[*ix]) {
+                        select(&ix, window, cx);
+                    }
+                }
+            })
+            .on_key_down({
+                let state = state.clone();
+                move |event: &KeyDownEvent, _, cx| {
+                    let modifiers = &event.keystroke.modifiers;
+                    if modifiers.control || modifiers.alt || modifiers.platform {
+                        return;
+                    }
+                    let Some(ch) = event
+                        .keystroke
+                        .key_char
+                        .as_ref()
+                        .and_then(|key| key.chars().next())
+                        .filter(|ch| ch.is_alphanumeric())
+                    else {
+                        return;
+                    };
+
+                    // Jump to the next item starting with the typed letter, wrapping
+                    // around so repeated presses cycle through the matches.
+                    let start = highlighted.map_or(0, |ix| ix + 1);
+                    let next = (start..item_count).chain(0..start).find(|ix| {
+                        !disabled[*ix]
+                            && labels[*ix]
+                                .chars()
+                                .next()
+                                .is_some_and(|first| first.to_lowercase().eq(ch.to_lowercase()))
+                    });
+                    if let Some(ix) = next {
+                        highlight(&state, ix, cx);
+                        cx.stop_propagation();
+                    }
+                }
+            })
+            .children(items);
+
+        let trigger = div().child(self.trigger).when(!controlled, |this| {
+            this.on_mouse_down(MouseButton::Left, move |_, _, cx| {
+                let open = state.read(cx).open;
+                set_open(&state, !open, cx);
+            })
+        });
 
-        Popover::new(self.id, self.trigger, menu.into_any_element()).open(self.open)
+        Popover::new(self.id, trigger.into_any_element(), menu.into_any_element())
+            .open(open)
+            .on_dismiss(dismiss)
     }
 }
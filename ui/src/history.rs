@@ -0,0 +1,105 @@
+use gpui::SharedString;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+use unicode_segmentation::*;
+
+const MAX_DEPTH: usize = 100;
+const GROUP_INTERVAL: Duration = Duration::from_millis(1000);
+
+#[derive(Clone)]
+pub(crate) struct Snapshot {
+    pub content: SharedString,
+    pub selected_range: Range<usize>,
+    pub selection_reversed: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Replace,
+}
+
+struct LastEdit {
+    kind: EditKind,
+    range: Range<usize>,
+    at: Instant,
+}
+
+/// Undo/redo stacks of text and selection snapshots. Consecutive single-grapheme
+/// insertions or deletions at adjacent offsets share one step.
+#[derive(Default)]
+pub(crate) struct History {
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    last_edit: Option<LastEdit>,
+}
+
+impl History {
+    pub fn record(&mut self, before: Snapshot, range: &Range<usize>, new_text: &str) {
+        if range.is_empty() && new_text.is_empty() {
+            return;
+        }
+
+        let single_grapheme = |text: &str| text.graphemes(true).count() == 1;
+        let kind = if range.is_empty() && single_grapheme(new_text) {
+            EditKind::Insert
+        } else if new_text.is_empty() && single_grapheme(&before.content[range.clone()]) {
+            EditKind::Delete
+        } else {
+            EditKind::Replace
+        };
+
+        let now = Instant::now();
+        let continues_group = self.last_edit.as_ref().is_some_and(|last| {
+            last.kind == kind
+                && now.duration_since(last.at) < GROUP_INTERVAL
+                && match kind {
+                    EditKind::Insert => {
+                        range.start == last.range.end && !new_text.trim().is_empty()
+                    }
+                    EditKind::Delete => {
+                        range.end == last.range.start || range.start == last.range.start
+                    }
+                    EditKind::Replace => false,
+                }
+        });
+
+        if !continues_group {
+            self.undo_stack.push(before);
+            if self.undo_stack.len() > MAX_DEPTH {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+        self.last_edit = Some(LastEdit {
+            kind,
+            range: range.start..range.start + new_text.len(),
+            at: now,
+        });
+    }
+
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.undo_stack.pop()?;
+        self.redo_stack.push(current);
+        self.last_edit = None;
+        Some(snapshot)
+    }
+
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.redo_stack.pop()?;
+        self.undo_stack.push(current);
+        self.last_edit = None;
+        Some(snapshot)
+    }
+
+    pub fn break_group(&mut self) {
+        self.last_edit = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_edit = None;
+    }
+}
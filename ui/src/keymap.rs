@@ -0,0 +1,355 @@
+use crate::date_picker::{
+    CloseCalendar, NextDay, NextMonth, NextWeek, OpenCalendar, PreviousDay, PreviousMonth,
+    PreviousWeek, SelectDate,
+};
+use crate::dialog::{Cancel, ConfirmAction, FocusNextAction, FocusPreviousAction};
+use crate::dropdown::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use crate::list::{
+    Confirm as ConfirmRow, ExtendSelectionDown, ExtendSelectionUp, SelectAll as SelectAllRows,
+    SelectFirst as SelectFirstRow, SelectLast as SelectLastRow, SelectNext as SelectNextRow,
+    SelectPrevious as SelectPreviousRow, ToggleSelected,
+};
+use crate::number_input::{Decrement, Increment};
+use crate::popover::Dismiss;
+use crate::slider::{Decrease, DecreasePage, Increase, IncreasePage, SetMax, SetMin};
+use crate::table::{
+    CancelEditing, ExtendDown, ExtendLeft, ExtendRight, ExtendUp, MoveDown, MoveLeft, MoveRight,
+    MoveUp, StartEditing,
+};
+use crate::text_area::{Down, Newline, PageDown, PageUp, SelectDown, SelectUp, Up};
+use crate::text_input::{
+    Backspace, Copy, Cut, Delete, DeleteWordLeft, DeleteWordRight, End, Enter, Home, Left, Paste,
+    Redo, Right, SelectAll, SelectLeft, SelectRight, SelectToEnd, SelectToHome, SelectWordLeft,
+    SelectWordRight, ShowCharacterPalette, Undo, WordLeft, WordRight,
+};
+use gpui::{App, DummyKeyboardMapper, KeyBinding, KeyBindingContextPredicate, NoAction};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fmt, fs, io, path::Path, rc::Rc};
+
+const EDITOR_CONTEXT: &str = "TextInput || TextArea";
+
+#[cfg(target_os = "macos")]
+const PRIMARY: &str = "cmd";
+#[cfg(not(target_os = "macos"))]
+const PRIMARY: &str = "ctrl";
+
+#[cfg(target_os = "macos")]
+const WORD: &str = "alt";
+#[cfg(not(target_os = "macos"))]
+const WORD: &str = "ctrl";
+
+/// Binds the default keys of every component.
+pub fn bind_defaults(cx: &mut App) {
+    cx.bind_keys(default_bindings());
+}
+
+fn default_bindings() -> Vec<KeyBinding> {
+    let editor = Some(EDITOR_CONTEXT);
+    let input = Some("TextInput");
+    let area = Some("TextArea");
+    let calendar = Some("Calendar");
+    let dropdown = Some("Dropdown");
+    let dialog = Some("Dialog");
+    let list = Some("List");
+    let slider = Some("Slider");
+    let table = Some("Table");
+
+    let mut bindings = vec![
+        KeyBinding::new("backspace", Backspace, editor),
+        KeyBinding::new("delete", Delete, editor),
+        KeyBinding::new("left", Left, editor),
+        KeyBinding::new("right", Right, editor),
+        KeyBinding::new("shift-left", SelectLeft, editor),
+        KeyBinding::new("shift-right", SelectRight, editor),
+        KeyBinding::new("home", Home, editor),
+        KeyBinding::new("end", End, editor),
+        KeyBinding::new(&format!("{PRIMARY}-a"), SelectAll, editor),
+        KeyBinding::new(&format!("{PRIMARY}-v"), Paste, editor),
+        KeyBinding::new(&format!("{PRIMARY}-c"), Copy, editor),
+        KeyBinding::new(&format!("{PRIMARY}-x"), Cut, editor),
+        KeyBinding::new(&format!("{PRIMARY}-z"), Undo, editor),
+        KeyBinding::new(&format!("{PRIMARY}-shift-z"), Redo, editor),
+        KeyBinding::new(&format!("{WORD}-left"), WordLeft, input),
+        KeyBinding::new(&format!("{WORD}-right"), WordRight, input),
+        KeyBinding::new(&format!("{WORD}-shift-left"), SelectWordLeft, input),
+        KeyBinding::new(&format!("{WORD}-shift-right"), SelectWordRight, input),
+        KeyBinding::new(&format!("{WORD}-backspace"), DeleteWordLeft, input),
+        KeyBinding::new(&format!("{WORD}-delete"), DeleteWordRight, input),
+        KeyBinding::new("shift-home", SelectToHome, input),
+        KeyBinding::new("shift-end", SelectToEnd, input),
+        KeyBinding::new("enter", Enter, input),
+        KeyBinding::new("up", Up, area),
+        KeyBinding::new("down", Down, area),
+        KeyBinding::new("shift-up", SelectUp, area),
+        KeyBinding::new("shift-down", SelectDown, area),
+        KeyBinding::new("pageup", PageUp, area),
+        KeyBinding::new("pagedown", PageDown, area),
+        KeyBinding::new("enter", Newline, area),
+        KeyBinding::new("up", Increment, Some("NumberInput")),
+        KeyBinding::new("down", Decrement, Some("NumberInput")),
+        KeyBinding::new("alt-down", OpenCalendar, Some("DatePicker")),
+        KeyBinding::new("escape", CloseCalendar, calendar),
+        KeyBinding::new("left", PreviousDay, calendar),
+        KeyBinding::new("right", NextDay, calendar),
+        KeyBinding::new("up", PreviousWeek, calendar),
+        KeyBinding::new("down", NextWeek, calendar),
+        KeyBinding::new("pageup", PreviousMonth, calendar),
+        KeyBinding::new("pagedown", NextMonth, calendar),
+        KeyBinding::new("enter", SelectDate, calendar),
+        KeyBinding::new("space", SelectDate, calendar),
+        KeyBinding::new("up", SelectPrevious, dropdown),
+        KeyBinding::new("down", SelectNext, dropdown),
+        KeyBinding::new("home", SelectFirst, dropdown),
+        KeyBinding::new("end", SelectLast, dropdown),
+        KeyBinding::new("enter", Confirm, dropdown),
+        KeyBinding::new("space", Confirm, dropdown),
+        KeyBinding::new("escape", Dismiss, Some("Popover")),
+        KeyBinding::new("up", SelectPreviousRow, list),
+        KeyBinding::new("down", SelectNextRow, list),
+        KeyBinding::new("home", SelectFirstRow, list),
+        KeyBinding::new("end", SelectLastRow, list),
+        KeyBinding::new("shift-up", ExtendSelectionUp, list),
+        KeyBinding::new("shift-down", ExtendSelectionDown, list),
+        KeyBinding::new("space", ToggleSelected, list),
+        KeyBinding::new(&format!("{PRIMARY}-a"), SelectAllRows, list),
+        KeyBinding::new("enter", ConfirmRow, list),
+        KeyBinding::new("up", MoveUp, table),
+        KeyBinding::new("down", MoveDown, table),
+        KeyBinding::new("left", MoveLeft, table),
+        KeyBinding::new("right", MoveRight, table),
+        KeyBinding::new("tab", MoveRight, table),
+        KeyBinding::new("shift-tab", MoveLeft, table),
+        KeyBinding::new("shift-up", ExtendUp, table),
+        KeyBinding::new("shift-down", ExtendDown, table),
+        KeyBinding::new("shift-left", ExtendLeft, table),
+        KeyBinding::new("shift-right", ExtendRight, table),
+        KeyBinding::new("enter", StartEditing, table),
+        KeyBinding::new("f2", StartEditing, table),
+        KeyBinding::new("escape", CancelEditing, table),
+        KeyBinding::new("left", Decrease, slider),
+        KeyBinding::new("down", Decrease, slider),
+        KeyBinding::new("right", Increase, slider),
+        KeyBinding::new("up", Increase, slider),
+        KeyBinding::new("pagedown", DecreasePage, slider),
+        KeyBinding::new("pageup", IncreasePage, slider),
+        KeyBinding::new("home", SetMin, slider),
+        KeyBinding::new("end", SetMax, slider),
+        KeyBinding::new("escape", Cancel, dialog),
+        KeyBinding::new("enter", ConfirmAction, dialog),
+        KeyBinding::new("space", ConfirmAction, dialog),
+        KeyBinding::new("tab", FocusNextAction, dialog),
+        KeyBinding::new("right", FocusNextAction, dialog),
+        KeyBinding::new("shift-tab", FocusPreviousAction, dialog),
+        KeyBinding::new("left", FocusPreviousAction, dialog),
+    ];
+
+    if cfg!(target_os = "macos") {
+        bindings.extend([
+            KeyBinding::new("ctrl-cmd-space", ShowCharacterPalette, editor),
+            KeyBinding::new("cmd-left", Home, input),
+            KeyBinding::new("cmd-right", End, input),
+            KeyBinding::new("cmd-shift-left", SelectToHome, input),
+            KeyBinding::new("cmd-shift-right", SelectToEnd, input),
+        ]);
+    } else {
+        bindings.extend([
+            KeyBinding::new("ctrl-.", ShowCharacterPalette, editor),
+            KeyBinding::new("ctrl-y", Redo, editor),
+        ]);
+    }
+
+    bindings
+}
+
+/// A problem found while loading a keymap file with [`load_keymap_file`].
+#[derive(Debug)]
+pub enum KeymapError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    InvalidContext { context: String, message: String },
+    UnknownAction { keystrokes: String, action: String },
+    InvalidKeystroke { keystrokes: String, message: String },
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapError::Io(error) => write!(f, "failed to read keymap: {error}"),
+            KeymapError::Json(error) => write!(f, "invalid keymap: {error}"),
+            KeymapError::InvalidContext { context, message } => {
+                write!(f, "invalid context \"{context}\": {message}")
+            }
+            KeymapError::UnknownAction { keystrokes, action } => {
+                write!(f, "unknown action \"{action}\" bound to \"{keystrokes}\"")
+            }
+            KeymapError::InvalidKeystroke {
+                keystrokes,
+                message,
+            } => write!(f, "invalid keystroke \"{keystrokes}\": {message}"),
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeymapSection {
+    #[serde(default)]
+    context: Option<String>,
+    bindings: BTreeMap<String, Option<String>>,
+}
+
+/// Binds the keys from a JSON keymap on top of the defaults from [`crate::init`].
+///
+/// The file holds a list of sections, each with an optional key `context`
+/// (e.g. `"TextInput"`) and a map from keystrokes to action names such as
+/// `"text_input::SelectAll"`. Binding a keystroke to `null` unbinds it. A
+/// missing file is not an error. Every valid binding is applied even when
+/// others fail, and all failures are returned together.
+pub fn load_keymap_file(path: impl AsRef<Path>, cx: &mut App) -> Result<(), Vec<KeymapError>> {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(vec![KeymapError::Io(error)]),
+    };
+    load_keymap(&source, cx)
+}
+
+fn load_keymap(source: &str, cx: &mut App) -> Result<(), Vec<KeymapError>> {
+    let sections: Vec<KeymapSection> =
+        serde_json::from_str(source).map_err(|error| vec![KeymapError::Json(error)])?;
+
+    let mut bindings = Vec::new();
+    let mut errors = Vec::new();
+
+    for section in sections {
+        let predicate = match section.context {
+            Some(context) => match KeyBindingContextPredicate::parse(&context) {
+                Ok(predicate) => Some(Rc::new(predicate)),
+                Err(error) => {
+                    errors.push(KeymapError::InvalidContext {
+                        context,
+                        message: error.to_string(),
+                    });
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        for (keystrokes, action) in section.bindings {
+            let action = match action {
+                Some(name) => match cx.build_action(&name, None) {
+                    Ok(action) => action,
+                    Err(_) => {
+                        errors.push(KeymapError::UnknownAction {
+                            keystrokes,
+                            action: name,
+                        });
+                        continue;
+                    }
+                },
+                None => Box::new(NoAction),
+            };
+
+            match KeyBinding::load(
+                &keystrokes,
+                action,
+                predicate.clone(),
+                false,
+                None,
+                &DummyKeyboardMapper,
+            ) {
+                Ok(binding) => bindings.push(binding),
+                Err(error) => errors.push(KeymapError::InvalidKeystroke {
+                    keystrokes,
+                    message: error.to_string(),
+                }),
+            }
+        }
+    }
+
+    cx.bind_keys(bindings);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{Keystroke, TestAppContext};
+
+    fn bound_action(keystroke: &str, cx: &App) -> Option<&'static str> {
+        let keystroke = Keystroke::parse(keystroke).unwrap();
+        cx.all_bindings_for_input(&[keystroke])
+            .first()
+            .map(|binding| binding.action().name())
+    }
+
+    #[gpui::test]
+    fn binds_valid_sections(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let source = r#"[
+                { "context": "TextInput", "bindings": { "ctrl-k": "text_input::SelectAll" } },
+                { "bindings": { "ctrl-j": "popover::Dismiss" } }
+            ]"#;
+            assert!(load_keymap(source, cx).is_ok());
+            assert_eq!(bound_action("ctrl-k", cx), Some("text_input::SelectAll"));
+            assert_eq!(bound_action("ctrl-j", cx), Some("popover::Dismiss"));
+        });
+    }
+
+    #[gpui::test]
+    fn null_unbinds_a_keystroke(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let source = r#"[{ "context": "TextInput", "bindings": { "ctrl-k": null } }]"#;
+            assert!(load_keymap(source, cx).is_ok());
+            assert_eq!(bound_action("ctrl-k", cx), Some("zed::NoAction"));
+        });
+    }
+
+    #[gpui::test]
+    fn collects_every_error_and_keeps_valid_bindings(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let source = r#"[
+                { "context": "TextInput &&", "bindings": { "ctrl-k": "text_input::SelectAll" } },
+                { "bindings": {
+                    "ctrl-l": "text_input::Missing",
+                    "ctrl-a-b": "text_input::SelectAll",
+                    "ctrl-m": "text_input::Undo"
+                } }
+            ]"#;
+            let errors = load_keymap(source, cx).unwrap_err();
+            assert!(matches!(
+                errors.as_slice(),
+                [
+                    KeymapError::InvalidContext { .. },
+                    KeymapError::InvalidKeystroke { .. },
+                    KeymapError::UnknownAction { .. },
+                ]
+            ));
+            assert_eq!(bound_action("ctrl-k", cx), None);
+            assert_eq!(bound_action("ctrl-m", cx), Some("text_input::Undo"));
+        });
+    }
+
+    #[gpui::test]
+    fn rejects_malformed_files(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            for source in ["{", r#"[{ "bindings": {}, "when": "TextInput" }]"#] {
+                let errors = load_keymap(source, cx).unwrap_err();
+                assert!(matches!(errors.as_slice(), [KeymapError::Json(_)]));
+            }
+        });
+    }
+
+    #[gpui::test]
+    fn missing_file_is_not_an_error(cx: &mut TestAppContext) {
+        cx.update(|cx| assert!(load_keymap_file("does-not-exist.json", cx).is_ok()));
+    }
+}
@@ -1,11 +1,54 @@
 mod button;
+mod date;
+mod date_picker;
+mod dialog;
 mod dropdown;
+mod history;
+mod keymap;
+mod list;
+mod mask;
+mod number_input;
 mod popover;
+mod progress;
+mod select;
+mod slider;
+mod table;
+mod text_area;
 mod text_input;
+mod text_utils;
 mod theme;
+mod theme_file;
+mod toast;
+mod tooltip;
+mod validator;
 
 pub use button::{Button, ButtonVariant};
+pub use date::{Date, days_in_month};
+pub use date_picker::*;
+pub use dialog::{Dialog, DialogEvent};
 pub use dropdown::{Dropdown, MenuItem};
+pub use keymap::{KeymapError, load_keymap_file};
+pub use list::{List, ListEvent, SelectionMode};
+pub use mask::InputMask;
+pub use number_input::*;
 pub use popover::Popover;
+pub use progress::{ProgressBar, Spinner};
+pub use select::Select;
+pub use slider::{Slider, SliderEvent};
+pub use table::{CellPosition, CellRange, Column, Table, TableEvent};
+pub use text_area::*;
 pub use text_input::*;
-pub use theme::{Theme, ThemeAble};
+pub use theme::{Radius, Spacing, Theme, ThemeAble, ThemeMode, Typography};
+pub use theme_file::ThemeError;
+pub use toast::{Toast, ToastId, ToastLevel, ToastStack};
+pub use tooltip::{Tooltip, TooltipPlacement};
+pub use validator::Validator;
+
+use gpui::App;
+
+/// Sets up the global theme and binds the default keymap of the components.
+/// Call this once before opening any window.
+pub fn init(cx: &mut App) {
+    Theme::init(cx);
+    keymap::bind_defaults(cx);
+}
@@ -0,0 +1,472 @@
+use crate::theme::ThemeAble;
+use gpui::{
+    AnyElement, App, ClickEvent, Context, EventEmitter, FocusHandle, Focusable, ScrollStrategy,
+    UniformListScrollHandle, Window, actions, div, prelude::*, uniform_list,
+};
+use std::ops::Range;
+use std::rc::Rc;
+
+actions!(
+    list,
+    [
+        SelectPrevious,
+        SelectNext,
+        SelectFirst,
+        SelectLast,
+        ExtendSelectionUp,
+        ExtendSelectionDown,
+        ToggleSelected,
+        SelectAll,
+        Confirm
+    ]
+);
+
+type RenderItem = Rc<dyn Fn(usize, &mut Window, &mut App) -> AnyElement>;
+
+/// Selected rows as sorted, disjoint ranges with gaps between them, so that
+/// selecting all of a long list costs no more than selecting one row.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Selection {
+    ranges: Vec<Range<usize>>,
+}
+
+impl Selection {
+    fn range(range: Range<usize>) -> Self {
+        Self {
+            ranges: if range.is_empty() {
+                Vec::new()
+            } else {
+                vec![range]
+            },
+        }
+    }
+
+    // The index of the first range that ends after `ix`.
+    fn position(&self, ix: usize) -> usize {
+        self.ranges.partition_point(|range| range.end <= ix)
+    }
+
+    fn contains(&self, ix: usize) -> bool {
+        self.ranges
+            .get(self.position(ix))
+            .is_some_and(|range| range.start <= ix)
+    }
+
+    fn toggle(&mut self, ix: usize) {
+        let position = self.position(ix);
+        match self.ranges.get(position).cloned() {
+            Some(range) if range.start <= ix => {
+                let rest = [range.start..ix, ix + 1..range.end];
+                self.ranges.splice(
+                    position..=position,
+                    rest.into_iter().filter(|range| !range.is_empty()),
+                );
+            }
+            _ => {
+                self.ranges.insert(position, ix..ix + 1);
+                if self
+                    .ranges
+                    .get(position + 1)
+                    .is_some_and(|next| next.start == ix + 1)
+                {
+                    self.ranges[position].end = self.ranges.remove(position + 1).end;
+                }
+                if position > 0 && self.ranges[position - 1].end == ix {
+                    self.ranges[position - 1].end = self.ranges.remove(position).end;
+                }
+            }
+        }
+    }
+
+    /// Drops the rows from `len` on, returning whether any were selected.
+    fn truncate(&mut self, len: usize) -> bool {
+        if self.ranges.last().is_none_or(|range| range.end <= len) {
+            return false;
+        }
+        let position = self.position(len);
+        if self.ranges[position].start < len {
+            self.ranges[position].end = len;
+            self.ranges.truncate(position + 1);
+        } else {
+            self.ranges.truncate(position);
+        }
+        true
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ranges.iter().flat_map(|range| range.clone())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionMode {
+    #[default]
+    Single,
+    /// Shift extends the selection and Ctrl (Cmd on macOS) or Space toggles
+    /// single rows.
+    Multiple,
+}
+
+pub enum ListEvent {
+    /// The selected rows changed; read them with [`List::selection`].
+    SelectionChanged,
+    /// A row was double-clicked or Enter was pressed on it.
+    Confirmed(usize),
+}
+
+/// A scrolling list of `item_count` rows of equal height. Only the rows in
+/// view are rendered, by calling `render_item` with their index, so the list
+/// stays cheap no matter how many items the data source has.
+pub struct List {
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    item_count: usize,
+    render_item: RenderItem,
+    mode: SelectionMode,
+    selection: Selection,
+    // The row moved by the keyboard, and where a shift-extended range starts.
+    cursor: Option<usize>,
+    anchor: Option<usize>,
+    // The first row rendered last frame, i.e. the one at the top of the view.
+    top_row: usize,
+}
+
+impl List {
+    pub fn new(
+        item_count: usize,
+        render_item: impl Fn(usize, &mut Window, &mut App) -> AnyElement + 'static,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            item_count,
+            render_item: Rc::new(render_item),
+            mode: SelectionMode::default(),
+            selection: Selection::default(),
+            cursor: None,
+            anchor: None,
+            top_row: 0,
+        }
+    }
+
+    pub fn selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn item_count(&self) -> usize {
+        self.item_count
+    }
+
+    /// Updates the number of rows after the data source changed. Selected
+    /// rows past the new end are deselected.
+    pub fn set_item_count(&mut self, item_count: usize, cx: &mut Context<Self>) {
+        self.item_count = item_count;
+        self.cursor = self.cursor.filter(|ix| *ix < item_count);
+        self.anchor = self.anchor.filter(|ix| *ix < item_count);
+        if self.selection.truncate(item_count) {
+            cx.emit(ListEvent::SelectionChanged);
+        }
+        cx.notify();
+    }
+
+    /// The most recently selected row, if it is still selected.
+    pub fn selected(&self) -> Option<usize> {
+        self.cursor.filter(|ix| self.selection.contains(*ix))
+    }
+
+    /// All selected rows in ascending order.
+    pub fn selection(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selection.iter()
+    }
+
+    pub fn is_selected(&self, ix: usize) -> bool {
+        self.selection.contains(ix)
+    }
+
+    /// Selects only the row at `ix`, or nothing, and scrolls it into view.
+    pub fn select(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        let ix = ix.filter(|ix| *ix < self.item_count);
+        self.anchor = ix;
+        let selection = ix.map_or_else(Selection::default, |ix| Selection::range(ix..ix + 1));
+        self.set_selection(selection, ix, cx);
+    }
+
+    fn set_selection(
+        &mut self,
+        selection: Selection,
+        cursor: Option<usize>,
+        cx: &mut Context<Self>,
+    ) {
+        self.cursor = cursor;
+        if let Some(cursor) = cursor {
+            self.scroll_into_view(cursor);
+        }
+        if selection != self.selection {
+            self.selection = selection;
+            cx.emit(ListEvent::SelectionChanged);
+        }
+        cx.notify();
+    }
+
+    /// Scrolls as little as possible to bring the row at `ix` fully into
+    /// view: rows above the viewport end up at its top and rows below it at
+    /// its bottom. Rows already in view don't scroll at all.
+    fn scroll_into_view(&self, ix: usize) {
+        let strategy = if ix <= self.top_row {
+            ScrollStrategy::Top
+        } else {
+            ScrollStrategy::Bottom
+        };
+        self.scroll_handle.scroll_to_item(ix, strategy);
+    }
+
+    fn extend_to(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let anchor = *self.anchor.get_or_insert(ix);
+        let range = anchor.min(ix)..anchor.max(ix) + 1;
+        self.set_selection(Selection::range(range), Some(ix), cx);
+    }
+
+    fn move_to(&mut self, ix: usize, extend: bool, cx: &mut Context<Self>) {
+        if extend && self.mode == SelectionMode::Multiple {
+            self.extend_to(ix, cx);
+        } else {
+            self.select(Some(ix), cx);
+        }
+    }
+
+    fn previous_index(&self) -> Option<usize> {
+        let last = self.item_count.checked_sub(1)?;
+        Some(self.cursor.map_or(last, |ix| ix.saturating_sub(1)))
+    }
+
+    fn next_index(&self) -> Option<usize> {
+        let last = self.item_count.checked_sub(1)?;
+        Some(self.cursor.map_or(0, |ix| (ix + 1).min(last)))
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.previous_index() {
+            self.move_to(ix, false, cx);
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.next_index() {
+            self.move_to(ix, false, cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if self.item_count > 0 {
+            self.move_to(0, false, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(last) = self.item_count.checked_sub(1) {
+            self.move_to(last, false, cx);
+        }
+    }
+
+    fn extend_selection_up(
+        &mut self,
+        _: &ExtendSelectionUp,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.previous_index() {
+            self.move_to(ix, true, cx);
+        }
+    }
+
+    fn extend_selection_down(
+        &mut self,
+        _: &ExtendSelectionDown,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(ix) = self.next_index() {
+            self.move_to(ix, true, cx);
+        }
+    }
+
+    fn toggle_selected(&mut self, _: &ToggleSelected, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.cursor {
+            self.toggle(ix, cx);
+        }
+    }
+
+    fn toggle(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if self.mode == SelectionMode::Single {
+            self.select(Some(ix), cx);
+            return;
+        }
+        let mut selection = self.selection.clone();
+        selection.toggle(ix);
+        self.anchor = Some(ix);
+        self.set_selection(selection, Some(ix), cx);
+    }
+
+    fn select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
+        if self.mode == SelectionMode::Multiple {
+            self.set_selection(Selection::range(0..self.item_count), self.cursor, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected() {
+            cx.emit(ListEvent::Confirmed(ix));
+        }
+    }
+
+    fn click(&mut self, ix: usize, event: &ClickEvent, cx: &mut Context<Self>) {
+        let modifiers = event.modifiers();
+        if modifiers.shift && self.mode == SelectionMode::Multiple {
+            self.extend_to(ix, cx);
+        } else if modifiers.secondary() {
+            self.toggle(ix, cx);
+        } else {
+            self.select(Some(ix), cx);
+            if event.click_count() == 2 {
+                cx.emit(ListEvent::Confirmed(ix));
+            }
+        }
+    }
+
+    fn render_rows(
+        &mut self,
+        range: Range<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let focused = self.focus_handle.is_focused(window);
+        let theme = cx.theme();
+        let (selection, highlight, focus_ring, transparent, padding) = (
+            theme.selection,
+            theme.highlight,
+            theme.focus_ring,
+            theme.transparent,
+            theme.spacing.md,
+        );
+        let render_item = self.render_item.clone();
+
+        self.top_row = range.start;
+        range
+            .map(|ix| {
+                let selected = self.selection.contains(ix);
+                div()
+                    .id(ix)
+                    .px(padding)
+                    .border_1()
+                    .border_color(if focused && self.cursor == Some(ix) {
+                        focus_ring
+                    } else {
+                        transparent
+                    })
+                    .when(selected, |this| this.bg(selection))
+                    .when(!selected, |this| this.hover(|this| this.bg(highlight)))
+                    .on_click(
+                        cx.listener(move |this, event: &ClickEvent, _, cx| {
+                            this.click(ix, event, cx)
+                        }),
+                    )
+                    .child(render_item(ix, window, cx))
+                    .into_any_element()
+            })
+            .collect()
+    }
+}
+
+impl Render for List {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focused = self.focus_handle.is_focused(window);
+        let theme = cx.theme();
+
+        div()
+            .size_full()
+            .border_1()
+            .border_color(if focused {
+                theme.focus_ring
+            } else {
+                theme.border
+            })
+            .rounded(theme.radius.md)
+            .bg(theme.surface)
+            .overflow_hidden()
+            .key_context("List")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::extend_selection_up))
+            .on_action(cx.listener(Self::extend_selection_down))
+            .on_action(cx.listener(Self::toggle_selected))
+            .on_action(cx.listener(Self::select_all))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                uniform_list("items", self.item_count, cx.processor(Self::render_rows))
+                    .track_scroll(self.scroll_handle.clone())
+                    .size_full(),
+            )
+    }
+}
+
+impl EventEmitter<ListEvent> for List {}
+
+impl Focusable for List {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggling_splits_and_merges_ranges() {
+        let mut selection = Selection::range(0..100_000);
+        selection.toggle(500);
+        assert_eq!(selection.ranges, vec![0..500, 501..100_000]);
+        assert!(!selection.contains(500));
+        assert!(selection.contains(501));
+
+        selection.toggle(0);
+        selection.toggle(99_999);
+        assert_eq!(selection.ranges, vec![1..500, 501..99_999]);
+
+        selection.toggle(500);
+        assert_eq!(selection.ranges, vec![1..99_999]);
+    }
+
+    #[test]
+    fn toggling_joins_neighbouring_rows() {
+        let mut selection = Selection::default();
+        for ix in [5, 3, 9] {
+            selection.toggle(ix);
+        }
+        assert_eq!(selection.ranges, vec![3..4, 5..6, 9..10]);
+
+        selection.toggle(4);
+        assert_eq!(selection.ranges, vec![3..6, 9..10]);
+        assert_eq!(selection.iter().collect::<Vec<_>>(), vec![3, 4, 5, 9]);
+    }
+
+    #[test]
+    fn truncating_drops_rows_past_the_end() {
+        let mut selection = Selection::range(2..4);
+        selection.toggle(6);
+        selection.toggle(8);
+
+        assert!(!selection.truncate(9));
+        assert!(selection.truncate(7));
+        assert_eq!(selection.ranges, vec![2..4, 6..7]);
+        assert!(selection.truncate(5));
+        assert_eq!(selection.ranges, vec![2..4]);
+        assert!(selection.truncate(3));
+        assert_eq!(selection.ranges, vec![2..3]);
+    }
+}
@@ -0,0 +1,189 @@
+#[derive(Clone, Copy, PartialEq)]
+enum Slot {
+    Digit,
+    Letter,
+    Alphanumeric,
+}
+
+impl Slot {
+    fn accepts(self, ch: char) -> bool {
+        match self {
+            Slot::Digit => ch.is_ascii_digit(),
+            Slot::Letter => ch.is_alphabetic(),
+            Slot::Alphanumeric => ch.is_alphanumeric(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Token {
+    Slot(Slot),
+    Literal(char),
+}
+
+/// A fixed input format for [`crate::TextInput`].
+///
+/// In the pattern `9` accepts a digit, `a` a letter and `*` either; any other
+/// character (or one escaped with `\`) is a literal separator that the input
+/// inserts on its own. For example `InputMask::new("99.99.9999")` formats
+/// `"01022025"` as `"01.02.2025"`.
+#[derive(Clone)]
+pub struct InputMask {
+    tokens: Vec<Token>,
+    ghost: Vec<char>,
+}
+
+impl InputMask {
+    pub fn new(pattern: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(ch) = chars.next() {
+            tokens.push(match ch {
+                '9' => Token::Slot(Slot::Digit),
+                'a' => Token::Slot(Slot::Letter),
+                '*' => Token::Slot(Slot::Alphanumeric),
+                '\\' => Token::Literal(chars.next().unwrap_or('\\')),
+                ch => Token::Literal(ch),
+            });
+        }
+
+        let ghost = tokens
+            .iter()
+            .map(|token| match token {
+                Token::Slot(_) => '_',
+                Token::Literal(ch) => *ch,
+            })
+            .collect();
+
+        Self { tokens, ghost }
+    }
+
+    /// The text shown in place of the slots that haven't been filled yet, e.g.
+    /// `"DD.MM.YYYY"`. Characters beyond the pattern length are ignored.
+    pub fn ghost(mut self, ghost: &str) -> Self {
+        for (slot, ch) in self.ghost.iter_mut().zip(ghost.chars()) {
+            *slot = ch;
+        }
+        self
+    }
+
+    /// Formats `text` against the pattern, dropping characters no slot accepts
+    /// and inserting literals. A literal is only emitted once input follows it,
+    /// unless `text` itself ends with that literal.
+    pub fn apply(&self, text: &str) -> String {
+        let mut formatted = String::new();
+        let mut chars = text.chars().peekable();
+
+        for token in &self.tokens {
+            match *token {
+                Token::Literal(literal) => {
+                    let Some(&next) = chars.peek() else {
+                        break;
+                    };
+                    if next == literal {
+                        chars.next();
+                    } else if !chars.clone().any(|ch| self.accepts(ch)) {
+                        break;
+                    }
+                    formatted.push(literal);
+                }
+                Token::Slot(slot) => match chars.by_ref().find(|ch| slot.accepts(*ch)) {
+                    Some(ch) => formatted.push(ch),
+                    None => break,
+                },
+            }
+        }
+
+        formatted
+    }
+
+    /// The characters of already formatted `text` that fill slots.
+    pub fn unmask(&self, text: &str) -> String {
+        text.chars()
+            .zip(&self.tokens)
+            .filter_map(|(ch, token)| matches!(token, Token::Slot(_)).then_some(ch))
+            .collect()
+    }
+
+    /// The part of the ghost text not yet covered by formatted `text`.
+    pub fn ghost_after(&self, text: &str) -> String {
+        self.ghost.iter().skip(text.chars().count()).collect()
+    }
+
+    fn accepts(&self, ch: char) -> bool {
+        self.tokens
+            .iter()
+            .any(|token| matches!(token, Token::Slot(slot) if slot.accepts(ch)))
+    }
+
+    pub(crate) fn is_literal(&self, char_ix: usize) -> bool {
+        matches!(self.tokens.get(char_ix), Some(Token::Literal(_)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date_mask() -> InputMask {
+        InputMask::new("99.99.9999").ghost("DD.MM.YYYY")
+    }
+
+    #[test]
+    fn apply_inserts_literals_between_slots() {
+        let mask = date_mask();
+        assert_eq!(mask.apply("01022025"), "01.02.2025");
+        assert_eq!(mask.apply("01.02.2025"), "01.02.2025");
+        assert_eq!(mask.apply("0102"), "01.02");
+    }
+
+    #[test]
+    fn apply_emits_trailing_literal_only_when_typed() {
+        let mask = date_mask();
+        assert_eq!(mask.apply("01"), "01");
+        assert_eq!(mask.apply("01."), "01.");
+        assert_eq!(mask.apply(""), "");
+    }
+
+    #[test]
+    fn apply_drops_rejected_and_excess_characters() {
+        let mask = date_mask();
+        assert_eq!(mask.apply("0a1b"), "01");
+        assert_eq!(mask.apply("010220251999"), "01.02.2025");
+        assert_eq!(InputMask::new("99.99").apply("1x"), "1");
+    }
+
+    #[test]
+    fn apply_supports_letter_and_escaped_slots() {
+        assert_eq!(InputMask::new("aa-99").apply("ab12"), "ab-12");
+        assert_eq!(InputMask::new("aa-99").apply("1a"), "a");
+        assert_eq!(InputMask::new(r"\99-9").apply("12"), "91-2");
+    }
+
+    #[test]
+    fn unmask_keeps_slot_characters() {
+        let mask = date_mask();
+        assert_eq!(mask.unmask("01.02.2025"), "01022025");
+        assert_eq!(mask.unmask("01.0"), "010");
+        assert_eq!(mask.unmask(""), "");
+    }
+
+    #[test]
+    fn ghost_after_covers_the_unfilled_rest() {
+        let mask = date_mask();
+        assert_eq!(mask.ghost_after(""), "DD.MM.YYYY");
+        assert_eq!(mask.ghost_after("01.0"), "M.YYYY");
+        assert_eq!(mask.ghost_after("01.02.2025"), "");
+        assert_eq!(InputMask::new("99.99").ghost_after("1"), "_.__");
+        assert_eq!(InputMask::new("99").ghost("ABCD").ghost_after(""), "AB");
+    }
+
+    #[test]
+    fn is_literal_matches_separator_positions() {
+        let mask = date_mask();
+        assert!(!mask.is_literal(0));
+        assert!(mask.is_literal(2));
+        assert!(mask.is_literal(5));
+        assert!(!mask.is_literal(10));
+    }
+}
@@ -0,0 +1,308 @@
+use crate::text_input::{TextInput, TextInputEvent};
+use crate::validator::Validator;
+use gpui::{
+    App, Context, Entity, EventEmitter, FocusHandle, Focusable, Pixels, ScrollWheelEvent,
+    SharedString, Subscription, Window, actions, div, prelude::*, px,
+};
+
+const SCROLL_STEP: Pixels = px(20.);
+
+actions!(number_input, [Increment, Decrement]);
+
+pub enum NumberInputEvent {
+    ValueChanged(f64),
+}
+
+struct NumberValidator;
+
+impl Validator for NumberValidator {
+    fn allows(&self, ch: char) -> bool {
+        ch.is_ascii_digit() || ch == '.' || ch == '-'
+    }
+
+    fn validate(&self, content: &str) -> Result<(), SharedString> {
+        if content.is_empty() || content.parse::<f64>().is_ok() {
+            Ok(())
+        } else {
+            Err("Not a number".into())
+        }
+    }
+
+    fn is_pending(&self, content: &str) -> bool {
+        matches!(content, "-" | "." | "-.")
+    }
+}
+
+/// A [`TextInput`] for numbers. Up/Down and the scroll wheel step the value,
+/// and on blur or Enter the text is clamped to the range and formatted.
+pub struct NumberInput {
+    input: Entity<TextInput>,
+    value: Option<f64>,
+    min: f64,
+    max: f64,
+    step: f64,
+    precision: Option<usize>,
+    scroll_delta: Pixels,
+    _subscription: Subscription,
+}
+
+impl NumberInput {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| TextInput::new(window, cx).validator(NumberValidator));
+        let subscription = cx.subscribe(&input, |this, _, event, cx| match event {
+            TextInputEvent::Changed { new, .. } => this.on_text_changed(new, cx),
+            TextInputEvent::Submitted | TextInputEvent::Blurred => this.commit(cx),
+            TextInputEvent::Focused => {}
+        });
+
+        Self {
+            input,
+            value: None,
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+            step: 1.,
+            precision: None,
+            scroll_delta: px(0.),
+            _subscription: subscription,
+        }
+    }
+
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self
+    }
+
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// The number of decimal places the value is rounded to when it is
+    /// committed or stepped.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Replaces the value without emitting [`NumberInputEvent::ValueChanged`].
+    /// Unlike [`TextInput::set_content`], which does emit, this is meant for
+    /// syncing from a model, so inputs that mirror each other (such as the
+    /// two fields of a unit converter) don't echo changes back and forth.
+    pub fn set_value(&mut self, value: f64, cx: &mut Context<Self>) {
+        let value = self.round(self.clamp(value));
+        self.value = Some(value);
+        self.set_text(self.format(value), cx);
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    fn round(&self, value: f64) -> f64 {
+        match self.precision {
+            Some(_) => self.format(value).parse().unwrap_or(value),
+            None => value,
+        }
+    }
+
+    fn format(&self, value: f64) -> String {
+        match self.precision {
+            Some(precision) => format!("{value:.precision$}"),
+            None => value.to_string(),
+        }
+    }
+
+    fn set_text(&self, text: String, cx: &mut Context<Self>) {
+        self.input.update(cx, |input, cx| {
+            let end = text.len();
+            input.set_content(text, cx);
+            input.selected_range = end..end;
+            cx.notify();
+        });
+    }
+
+    fn update_value(&mut self, value: Option<f64>, cx: &mut Context<Self>) {
+        if self.value == value {
+            return;
+        }
+        self.value = value;
+        if let Some(value) = value {
+            cx.emit(NumberInputEvent::ValueChanged(value));
+        }
+        cx.notify();
+    }
+
+    fn on_text_changed(&mut self, text: &str, cx: &mut Context<Self>) {
+        match text.parse::<f64>() {
+            Ok(value) if (self.min..=self.max).contains(&value) => {
+                self.update_value(Some(value), cx)
+            }
+            Ok(_) => {}
+            Err(_) if text.is_empty() => self.update_value(None, cx),
+            Err(_) => {}
+        }
+    }
+
+    fn commit(&mut self, cx: &mut Context<Self>) {
+        let text = self.input.read(cx).content.clone();
+        if text.is_empty() {
+            self.update_value(None, cx);
+            return;
+        }
+
+        let value = text.parse::<f64>().ok().or(self.value);
+        let Some(value) = value.map(|value| self.round(self.clamp(value))) else {
+            self.set_text(String::new(), cx);
+            return;
+        };
+
+        let formatted = self.format(value);
+        if formatted != text.as_ref() {
+            self.set_text(formatted, cx);
+        }
+        self.update_value(Some(value), cx);
+    }
+
+    fn step_by(&mut self, steps: f64, cx: &mut Context<Self>) {
+        let current = self.value.unwrap_or_else(|| self.clamp(0.));
+        let value = self.round(self.clamp(current + self.step * steps));
+        self.set_text(self.format(value), cx);
+        self.update_value(Some(value), cx);
+    }
+
+    fn increment(&mut self, _: &Increment, _: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(1., cx);
+    }
+
+    fn decrement(&mut self, _: &Decrement, _: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(-1., cx);
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.focus_handle(cx).is_focused(window) {
+            return;
+        }
+        cx.stop_propagation();
+
+        self.scroll_delta += event.delta.pixel_delta(SCROLL_STEP).y;
+        while self.scroll_delta.abs() >= SCROLL_STEP {
+            let steps = self.scroll_delta.signum();
+            self.scroll_delta -= SCROLL_STEP * steps;
+            self.step_by(steps as f64, cx);
+        }
+    }
+}
+
+impl Render for NumberInput {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .w_full()
+            .key_context("NumberInput")
+            .on_action(cx.listener(Self::increment))
+            .on_action(cx.listener(Self::decrement))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            .child(self.input.clone())
+    }
+}
+
+impl EventEmitter<NumberInputEvent> for NumberInput {}
+
+impl Focusable for NumberInput {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.input.read(cx).focus_handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    fn text(input: &Entity<NumberInput>, cx: &mut gpui::VisualTestContext) -> String {
+        input.read_with(cx, |input, cx| input.input.read(cx).content.to_string())
+    }
+
+    #[test]
+    fn signs_and_points_alone_are_pending() {
+        for content in ["-", ".", "-."] {
+            assert!(NumberValidator.validate(content).is_err());
+            assert!(NumberValidator.is_pending(content));
+        }
+        assert!(!NumberValidator.is_pending("1-"));
+        assert_eq!(NumberValidator.validate("-1.5"), Ok(()));
+    }
+
+    #[gpui::test]
+    fn set_value_clamps_and_rounds(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let (input, cx) =
+            cx.add_window_view(|window, cx| NumberInput::new(window, cx).max(10.).precision(2));
+
+        input.update(cx, |input, cx| input.set_value(12., cx));
+        assert_eq!(input.read_with(cx, |input, _| input.value()), Some(10.));
+        assert_eq!(text(&input, cx), "10.00");
+
+        input.update(cx, |input, cx| input.set_value(1.005_1, cx));
+        assert_eq!(input.read_with(cx, |input, _| input.value()), Some(1.01));
+        assert_eq!(text(&input, cx), "1.01");
+    }
+
+    #[gpui::test]
+    fn steps_stay_on_the_precision(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let (input, cx) = cx.add_window_view(|window, cx| {
+            NumberInput::new(window, cx).min(0.).step(0.1).precision(1)
+        });
+
+        input.update(cx, |input, cx| {
+            input.step_by(-1., cx);
+            for _ in 0..3 {
+                input.step_by(1., cx);
+            }
+        });
+        assert_eq!(input.read_with(cx, |input, _| input.value()), Some(0.3));
+        assert_eq!(text(&input, cx), "0.3");
+    }
+
+    #[gpui::test]
+    fn commit_formats_or_restores_the_text(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let (input, cx) =
+            cx.add_window_view(|window, cx| NumberInput::new(window, cx).min(-5.).precision(1));
+
+        input.update(cx, |input, cx| {
+            input.set_text("12.345".into(), cx);
+            input.commit(cx);
+        });
+        assert_eq!(input.read_with(cx, |input, _| input.value()), Some(12.3));
+        assert_eq!(text(&input, cx), "12.3");
+
+        input.update(cx, |input, cx| {
+            input.set_text("-".into(), cx);
+            input.commit(cx);
+        });
+        assert_eq!(input.read_with(cx, |input, _| input.value()), Some(12.3));
+        assert_eq!(text(&input, cx), "12.3");
+
+        input.update(cx, |input, cx| {
+            input.set_text("-40".into(), cx);
+            input.commit(cx);
+        });
+        assert_eq!(input.read_with(cx, |input, _| input.value()), Some(-5.));
+        assert_eq!(text(&input, cx), "-5.0");
+    }
+}
@@ -1,17 +1,36 @@
 use crate::theme::ThemeAble;
 use gpui::{
-    AnyElement, Corner, ElementId, InteractiveElement, IntoElement, ParentElement, RenderOnce,
-    Styled, anchored, deferred, div, px,
+    AnyElement, App, Bounds, Corner, DismissEvent, ElementId, InteractiveElement, IntoElement,
+    MouseButton, ParentElement, Pixels, RenderOnce, Styled, Window, actions, anchored, deferred,
+    div, prelude::*, px,
 };
+use std::cell::Cell;
+use std::rc::Rc;
 
 const PRIORITY: usize = 10;
 
+actions!(popover, [Dismiss]);
+
+type DismissHandler = Rc<dyn Fn(&DismissEvent, &mut Window, &mut App)>;
+
+/// Content anchored to a trigger element.
+///
+/// A popover created with [`Popover::new`] is shown while the owner passes
+/// `open(true)`. One created with [`Popover::stateful`] keeps its own open
+/// state and toggles it when the trigger is pressed. Either kind is dismissed
+/// by a mouse-down outside the trigger and content, by Escape while focus is
+/// inside, or when the window loses focus.
 #[derive(IntoElement)]
 pub struct Popover {
     id: ElementId,
     anchor: Corner,
     trigger: AnyElement,
     content: AnyElement,
+    open: Option<bool>,
+    on_dismiss: Option<DismissHandler>,
+}
+
+struct PopoverState {
     open: bool,
 }
 
@@ -21,13 +40,21 @@ impl Popover {
             id,
             trigger,
             content,
-            open: false,
+            open: Some(false),
             anchor: Corner::TopLeft,
+            on_dismiss: None,
+        }
+    }
+
+    pub fn stateful(id: ElementId, trigger: AnyElement, content: AnyElement) -> Self {
+        Self {
+            open: None,
+            ..Self::new(id, trigger, content)
         }
     }
 
     pub fn open(mut self, open: bool) -> Self {
-        self.open = open;
+        self.open = Some(open);
         self
     }
 
@@ -35,34 +62,102 @@ impl Popover {
         self.anchor = anchor;
         self
     }
+
+    pub fn on_dismiss(
+        mut self,
+        listener: impl Fn(&DismissEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_dismiss = Some(Rc::new(listener));
+        self
+    }
 }
 
 impl RenderOnce for Popover {
-    fn render(self, _window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = self.open.is_none().then(|| {
+            window.use_keyed_state(self.id.clone(), cx, |_, _| PopoverState { open: false })
+        });
+        let open = match &state {
+            Some(state) => state.read(cx).open,
+            None => self.open.unwrap_or(false),
+        };
+
+        let dismiss = Rc::new({
+            let state = state.clone();
+            let on_dismiss = self.on_dismiss.clone();
+            move |window: &mut Window, cx: &mut App| {
+                if let Some(state) = &state {
+                    state.update(cx, |state, cx| {
+                        state.open = false;
+                        cx.notify();
+                    });
+                }
+                if let Some(on_dismiss) = &on_dismiss {
+                    on_dismiss(&DismissEvent, window, cx);
+                }
+            }
+        });
+
+        if open && !window.is_window_active() {
+            let dismiss = dismiss.clone();
+            window.defer(cx, move |window, cx| dismiss(window, cx));
+        }
+
+        let trigger_bounds: Rc<Cell<Option<Bounds<Pixels>>>> = Rc::default();
+        let trigger = div().child(self.trigger).when_some(state, |this, state| {
+            this.on_mouse_down(MouseButton::Left, move |_, _, cx| {
+                state.update(cx, |state, cx| {
+                    state.open = !state.open;
+                    cx.notify();
+                });
+            })
+        });
+
         let theme = cx.theme();
-        let mut el = div().id(self.id).child(self.trigger).w_full();
-
-        if self.open {
-            el = el.child(
-                deferred(
-                    anchored()
-                        .anchor(self.anchor)
-                        .snap_to_window_with_margin(px(8.0))
-                        .child(
-                            div()
-                                .w_full()
-                                .mt_0p5()
-                                .border_1()
-                                .border_color(theme.border)
-                                .shadow_xs()
-                                .rounded_md()
-                                .bg(theme.surface)
-                                .p_1()
-                                .child(self.content),
-                        ),
-                )
-                .with_priority(PRIORITY),
-            );
+        let mut el = div()
+            .on_children_prepainted({
+                let trigger_bounds = trigger_bounds.clone();
+                move |bounds, _, _| trigger_bounds.set(bounds.first().copied())
+            })
+            .id(self.id)
+            .w_full()
+            .child(trigger);
+
+        if open {
+            el = el
+                .key_context("Popover")
+                .on_action({
+                    let dismiss = dismiss.clone();
+                    move |_: &Dismiss, window, cx| dismiss(window, cx)
+                })
+                .child(
+                    deferred(
+                        anchored()
+                            .anchor(self.anchor)
+                            .snap_to_window_with_margin(px(8.0))
+                            .child(
+                                div()
+                                    .w_full()
+                                    .mt(theme.spacing.xs)
+                                    .border_1()
+                                    .border_color(theme.border)
+                                    .shadow_xs()
+                                    .rounded(theme.radius.md)
+                                    .bg(theme.surface)
+                                    .p(theme.spacing.sm)
+                                    .on_mouse_down_out(move |event, window, cx| {
+                                        let on_trigger = trigger_bounds
+                                            .get()
+                                            .is_some_and(|bounds| bounds.contains(&event.position));
+                                        if !on_trigger {
+                                            dismiss(window, cx);
+                                        }
+                                    })
+                                    .child(self.content),
+                            ),
+                    )
+                    .with_priority(PRIORITY),
+                );
         }
 
         el
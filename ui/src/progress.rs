@@ -0,0 +1,207 @@
+use crate::theme::ThemeAble;
+use gpui::{
+    Animation, AnimationExt, App, ElementId, IntoElement, ParentElement, Pixels, RenderOnce,
+    SharedString, Styled, Window, div, ease_in_out, prelude::*, px, relative,
+};
+use std::time::{Duration, Instant};
+
+const BAR_HEIGHT: Pixels = px(6.);
+const DOT_SIZE: Pixels = px(6.);
+const DOT_COUNT: usize = 3;
+const TRANSITION: Duration = Duration::from_millis(200);
+const SPIN_PERIOD: Duration = Duration::from_millis(900);
+
+struct ProgressState {
+    from: f32,
+    to: f32,
+    started: Instant,
+    // Bumped whenever the value changes so the transition restarts.
+    generation: usize,
+}
+
+impl ProgressState {
+    /// The fraction on screen at `now`, part way from `from` to `to`.
+    fn shown(&self, now: Instant) -> f32 {
+        let progress = (now - self.started).as_secs_f32() / TRANSITION.as_secs_f32();
+        self.from + (self.to - self.from) * ease_in_out(progress.min(1.))
+    }
+}
+
+/// A determinate progress bar for a `value` between 0 and 1, with an
+/// optional label after it. Changes to the value slide into place on their
+/// own, without the owner having to notify every frame. Values that aren't
+/// finite show an empty bar.
+#[derive(IntoElement)]
+pub struct ProgressBar {
+    id: ElementId,
+    value: f32,
+    label: Option<SharedString>,
+}
+
+impl ProgressBar {
+    pub fn new(id: impl Into<ElementId>, value: f32) -> Self {
+        Self {
+            id: id.into(),
+            value: if value.is_finite() {
+                value.clamp(0., 1.)
+            } else {
+                0.
+            },
+            label: None,
+        }
+    }
+
+    pub fn label(mut self, label: SharedString) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+impl RenderOnce for ProgressBar {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| ProgressState {
+            from: self.value,
+            to: self.value,
+            started: Instant::now(),
+            generation: 0,
+        });
+        let (from, to, generation) = state.update(cx, |state, _| {
+            if state.to != self.value {
+                // Start from wherever the bar is now, which is short of the
+                // old value if it changes again mid-transition.
+                let now = Instant::now();
+                state.from = state.shown(now);
+                state.to = self.value;
+                state.started = now;
+                state.generation += 1;
+            }
+            (state.from, state.to, state.generation)
+        });
+
+        let theme = cx.theme();
+        div()
+            .id(self.id)
+            .w_full()
+            .flex()
+            .items_center()
+            .gap(theme.spacing.md)
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .h(BAR_HEIGHT)
+                    .rounded_full()
+                    .bg(theme.border)
+                    .overflow_hidden()
+                    .child(
+                        div()
+                            .absolute()
+                            .left(px(0.))
+                            .top(px(0.))
+                            .h_full()
+                            .rounded_full()
+                            .bg(theme.primary)
+                            .with_animation(
+                                ("fill", generation),
+                                Animation::new(TRANSITION).with_easing(ease_in_out),
+                                move |this, delta| this.w(relative(from + (to - from) * delta)),
+                            ),
+                    ),
+            )
+            .when_some(self.label, |this, label| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .text_size(theme.text.xs)
+                        .text_color(theme.muted_foreground)
+                        .child(label),
+                )
+            })
+    }
+}
+
+/// An indeterminate busy indicator: a row of dots that pulse in turn for as
+/// long as it is rendered, with an optional label such as "Saving…".
+#[derive(IntoElement)]
+pub struct Spinner {
+    id: ElementId,
+    label: Option<SharedString>,
+}
+
+impl Spinner {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            label: None,
+        }
+    }
+
+    pub fn label(mut self, label: SharedString) -> Self {
+        self.label = Some(label);
+        self
+    }
+}
+
+impl RenderOnce for Spinner {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let color = theme.primary;
+
+        div()
+            .id(self.id)
+            .flex()
+            .items_center()
+            .gap(theme.spacing.md)
+            .child(div().flex().gap(theme.spacing.xs).with_animation(
+                "dots",
+                Animation::new(SPIN_PERIOD).repeat(),
+                move |this, delta| {
+                    this.children((0..DOT_COUNT).map(|ix| {
+                        // Each dot peaks a third of a period after the
+                        // one before it.
+                        let phase = (delta - ix as f32 / DOT_COUNT as f32).rem_euclid(1.);
+                        let level = 1. - (2. * phase - 1.).abs();
+                        div()
+                            .size(DOT_SIZE)
+                            .rounded_full()
+                            .bg(color)
+                            .opacity(0.25 + 0.75 * level)
+                    }))
+                },
+            ))
+            .when_some(self.label, |this, label| {
+                this.child(
+                    div()
+                        .text_size(theme.text.sm)
+                        .text_color(theme.muted_foreground)
+                        .child(label),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_finite_values_show_an_empty_bar() {
+        assert_eq!(ProgressBar::new("progress", f32::NAN).value, 0.);
+        assert_eq!(ProgressBar::new("progress", f32::INFINITY).value, 0.);
+        assert_eq!(ProgressBar::new("progress", 1.5).value, 1.);
+    }
+
+    #[test]
+    fn shown_fraction_follows_the_transition() {
+        let started = Instant::now();
+        let state = ProgressState {
+            from: 0.2,
+            to: 0.6,
+            started,
+            generation: 1,
+        };
+        assert_eq!(state.shown(started), 0.2);
+        assert!((state.shown(started + TRANSITION / 2) - 0.4).abs() < 1e-6);
+        assert_eq!(state.shown(started + TRANSITION * 2), 0.6);
+    }
+}
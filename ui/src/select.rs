@@ -0,0 +1,106 @@
+use gpui::{App, ElementId, IntoElement, RenderOnce, SharedString, Window};
+use std::rc::Rc;
+
+use crate::button::{Button, ButtonVariant};
+use crate::dropdown::{Dropdown, MenuItem};
+
+const CARET_ICON: &str = "icons/caret_down.svg";
+
+type ChangeHandler<T> = Rc<dyn Fn(&T, &mut Window, &mut App)>;
+
+/// Picks one value of `T` from a list of labelled options, shown as a
+/// [`Dropdown`] below a button with the current choice.
+#[derive(IntoElement)]
+pub struct Select<T: Clone + PartialEq + 'static> {
+    id: SharedString,
+    options: Vec<(T, SharedString)>,
+    disabled_options: Vec<T>,
+    value: Option<T>,
+    placeholder: SharedString,
+    disabled: bool,
+    on_change: Option<ChangeHandler<T>>,
+}
+
+impl<T: Clone + PartialEq + 'static> Select<T> {
+    pub fn new(id: SharedString, options: Vec<(T, SharedString)>) -> Self {
+        Self {
+            id,
+            options,
+            disabled_options: Vec::new(),
+            value: None,
+            placeholder: "Select…".into(),
+            disabled: false,
+            on_change: None,
+        }
+    }
+
+    pub fn value(mut self, value: Option<T>) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Shown in the button while no option is selected.
+    pub fn placeholder(mut self, placeholder: SharedString) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+
+    /// Options that are listed but can't be chosen.
+    pub fn disabled_options(mut self, disabled_options: Vec<T>) -> Self {
+        self.disabled_options = disabled_options;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Called with the chosen value whenever it differs from the current one.
+    pub fn on_change(mut self, listener: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_change = Some(Rc::new(listener));
+        self
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> RenderOnce for Select<T> {
+    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+        let selected = self
+            .value
+            .as_ref()
+            .and_then(|value| self.options.iter().position(|(option, _)| option == value));
+        let label = selected.map_or(self.placeholder, |ix| self.options[ix].1.clone());
+
+        let trigger = Button::new(format!("{}-trigger", self.id).into())
+            .variant(ButtonVariant::Outlined)
+            .with_label(label)
+            .with_icon(CARET_ICON.into())
+            .disabled(self.disabled);
+
+        if self.disabled {
+            return trigger.into_any_element();
+        }
+
+        let menu = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(ix, (value, label))| {
+                MenuItem::new(ElementId::named_usize(self.id.clone(), ix), label.clone())
+                    .disabled(self.disabled_options.contains(value))
+            })
+            .collect();
+
+        let values: Vec<T> = self.options.into_iter().map(|(value, _)| value).collect();
+        let on_change = self.on_change;
+
+        Dropdown::stateful(self.id.into(), trigger.into_any_element(), menu)
+            .selected(selected)
+            .on_select(move |ix, window, cx| {
+                if let Some(on_change) = on_change.as_ref().filter(|_| Some(*ix) != selected) {
+                    on_change(&values[*ix], window, cx);
+                }
+            })
+            .into_any_element()
+    }
+}
@@ -0,0 +1,378 @@
+use crate::theme::ThemeAble;
+use gpui::{
+    App, Bounds, Context, CursorStyle, DispatchPhase, EventEmitter, FocusHandle, Focusable,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, Window, actions,
+    canvas, div, prelude::*, px, relative,
+};
+use std::cell::Cell;
+use std::rc::Rc;
+
+const THUMB_SIZE: Pixels = px(16.);
+const TRACK_HEIGHT: Pixels = px(4.);
+const PAGE_STEPS: f64 = 10.;
+
+actions!(
+    slider,
+    [
+        Increase,
+        Decrease,
+        IncreasePage,
+        DecreasePage,
+        SetMin,
+        SetMax
+    ]
+);
+
+type ValueHandler = Rc<dyn Fn(&f64, &mut Window, &mut App)>;
+
+pub enum SliderEvent {
+    /// The value changed, possibly in the middle of a drag.
+    Changed(f64),
+    /// The user finished changing the value: a drag that moved it ended or
+    /// a key moved it.
+    Committed(f64),
+}
+
+/// A horizontal slider between `min` and `max` that snaps to `step`. It is
+/// dragged or clicked with the mouse, and moved with the arrow keys, Page
+/// Up/Down and Home/End while focused.
+pub struct Slider {
+    focus_handle: FocusHandle,
+    value: f64,
+    min: f64,
+    max: f64,
+    step: f64,
+    disabled: bool,
+    // The value when the current drag started.
+    drag_start: Option<f64>,
+    on_change: Option<ValueHandler>,
+    on_commit: Option<ValueHandler>,
+    track_bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
+}
+
+impl Slider {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            value: 0.,
+            min: 0.,
+            max: 100.,
+            step: 1.,
+            disabled: false,
+            drag_start: None,
+            on_change: None,
+            on_commit: None,
+            track_bounds: Rc::default(),
+        }
+    }
+
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = min;
+        self.value = self.snap(self.value);
+        self
+    }
+
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self.value = self.snap(self.value);
+        self
+    }
+
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self.value = self.snap(self.value);
+        self
+    }
+
+    /// Called with the value alongside every [`SliderEvent::Changed`].
+    pub fn on_change(mut self, listener: impl Fn(&f64, &mut Window, &mut App) + 'static) -> Self {
+        self.on_change = Some(Rc::new(listener));
+        self
+    }
+
+    /// Called with the value alongside every [`SliderEvent::Committed`].
+    pub fn on_commit(mut self, listener: impl Fn(&f64, &mut Window, &mut App) + 'static) -> Self {
+        self.on_commit = Some(Rc::new(listener));
+        self
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Replaces the value without emitting [`SliderEvent`]s.
+    pub fn set_value(&mut self, value: f64, cx: &mut Context<Self>) {
+        self.value = self.snap(value);
+        cx.notify();
+    }
+
+    pub fn disabled(&mut self, disabled: bool, cx: &mut Context<Self>) {
+        if self.disabled == disabled {
+            return;
+        }
+        self.disabled = disabled;
+        self.drag_start = None;
+        cx.notify();
+    }
+
+    fn snap(&self, value: f64) -> f64 {
+        let value = value.clamp(self.min, self.max.max(self.min));
+        if self.step <= 0. {
+            return value;
+        }
+        let snapped = self.min + ((value - self.min) / self.step).round() * self.step;
+        snapped.clamp(self.min, self.max.max(self.min))
+    }
+
+    fn fraction(&self) -> f32 {
+        if self.max <= self.min {
+            return 0.;
+        }
+        ((self.value - self.min) / (self.max - self.min)) as f32
+    }
+
+    /// Moves to the snapped `value`, returning whether that changed it.
+    fn update_value(&mut self, value: f64, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let value = self.snap(value);
+        if value == self.value {
+            return false;
+        }
+        self.value = value;
+        cx.emit(SliderEvent::Changed(value));
+        if let Some(on_change) = self.on_change.clone() {
+            on_change(&value, window, cx);
+        }
+        cx.notify();
+        true
+    }
+
+    fn commit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(SliderEvent::Committed(self.value));
+        if let Some(on_commit) = self.on_commit.clone() {
+            on_commit(&self.value, window, cx);
+        }
+    }
+
+    fn set_from_position(
+        &mut self,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bounds) = self.track_bounds.get() else {
+            return;
+        };
+        if bounds.size.width <= px(0.) {
+            return;
+        }
+        let fraction = ((position.x - bounds.left()) / bounds.size.width).clamp(0., 1.);
+        self.update_value(
+            self.min + (self.max - self.min) * fraction as f64,
+            window,
+            cx,
+        );
+    }
+
+    fn move_to(&mut self, value: f64, window: &mut Window, cx: &mut Context<Self>) {
+        if self.update_value(value, window, cx) {
+            self.commit(window, cx);
+        }
+    }
+
+    fn step_by(&mut self, steps: f64, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.value + self.step * steps, window, cx);
+    }
+
+    fn increase(&mut self, _: &Increase, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(1., window, cx);
+    }
+
+    fn decrease(&mut self, _: &Decrease, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(-1., window, cx);
+    }
+
+    fn increase_page(&mut self, _: &IncreasePage, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(PAGE_STEPS, window, cx);
+    }
+
+    fn decrease_page(&mut self, _: &DecreasePage, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_by(-PAGE_STEPS, window, cx);
+    }
+
+    fn set_min(&mut self, _: &SetMin, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.min, window, cx);
+    }
+
+    fn set_max(&mut self, _: &SetMax, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.max, window, cx);
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle);
+        self.drag_start = Some(self.value);
+        self.set_from_position(event.position, window, cx);
+        cx.notify();
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        event: &MouseMoveEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.drag_start.is_some() {
+            self.set_from_position(event.position, window, cx);
+        }
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(drag_start) = self.drag_start.take() {
+            if self.value != drag_start {
+                self.commit(window, cx);
+            }
+            cx.notify();
+        }
+    }
+}
+
+impl Render for Slider {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        let focused = self.focus_handle.is_focused(window);
+        let fraction = self.fraction();
+
+        let track = div()
+            .relative()
+            .w_full()
+            .h(TRACK_HEIGHT)
+            .rounded_full()
+            .bg(theme.border)
+            .child(
+                div()
+                    .absolute()
+                    .left(px(0.))
+                    .top(px(0.))
+                    .h_full()
+                    .w(relative(fraction))
+                    .rounded_full()
+                    .bg(theme.primary),
+            )
+            .child(
+                div()
+                    .absolute()
+                    .left(relative(fraction))
+                    .top((TRACK_HEIGHT - THUMB_SIZE) / 2.)
+                    .ml(-THUMB_SIZE / 2.)
+                    .size(THUMB_SIZE)
+                    .rounded_full()
+                    .border_2()
+                    .border_color(if focused {
+                        theme.focus_ring
+                    } else {
+                        theme.primary
+                    })
+                    .bg(theme.surface)
+                    .shadow_xs(),
+            );
+
+        div()
+            .on_children_prepainted({
+                let track_bounds = self.track_bounds.clone();
+                move |bounds, _, _| track_bounds.set(bounds.first().copied())
+            })
+            .w_full()
+            .h(THUMB_SIZE + px(4.))
+            .px(THUMB_SIZE / 2.)
+            .flex()
+            .items_center()
+            .key_context("Slider")
+            .track_focus(&self.focus_handle)
+            .when(self.disabled, |this| {
+                this.cursor(CursorStyle::OperationNotAllowed).opacity(0.6)
+            })
+            .when(!self.disabled, |this| {
+                this.cursor(CursorStyle::PointingHand)
+                    .on_action(cx.listener(Self::increase))
+                    .on_action(cx.listener(Self::decrease))
+                    .on_action(cx.listener(Self::increase_page))
+                    .on_action(cx.listener(Self::decrease_page))
+                    .on_action(cx.listener(Self::set_min))
+                    .on_action(cx.listener(Self::set_max))
+                    .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            })
+            .child(track)
+            .when(self.drag_start.is_some(), |this| {
+                // A drag keeps following the pointer after it leaves the
+                // slider, until the button is released anywhere.
+                let entity = cx.entity();
+                this.child(
+                    canvas(
+                        |_, _, _| {},
+                        move |_, _, window, _| {
+                            let slider = entity.clone();
+                            window.on_mouse_event(
+                                move |event: &MouseMoveEvent, phase, window, cx| {
+                                    if phase == DispatchPhase::Bubble {
+                                        slider.update(cx, |slider, cx| {
+                                            slider.on_mouse_move(event, window, cx)
+                                        });
+                                    }
+                                },
+                            );
+                            let slider = entity;
+                            window.on_mouse_event(
+                                move |event: &MouseUpEvent, phase, window, cx| {
+                                    if phase == DispatchPhase::Bubble
+                                        && event.button == MouseButton::Left
+                                    {
+                                        slider.update(cx, |slider, cx| {
+                                            slider.on_mouse_up(event, window, cx)
+                                        });
+                                    }
+                                },
+                            );
+                        },
+                    )
+                    .absolute()
+                    .size_0(),
+                )
+            })
+    }
+}
+
+impl EventEmitter<SliderEvent> for Slider {}
+
+impl Focusable for Slider {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use std::cell::RefCell;
+
+    #[gpui::test]
+    fn commits_only_when_the_value_moves(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let commits = Rc::new(RefCell::new(Vec::new()));
+        let (slider, cx) = cx.add_window_view(|_, cx| {
+            let commits = commits.clone();
+            Slider::new(cx)
+                .max(10.)
+                .on_commit(move |value, _, _| commits.borrow_mut().push(*value))
+        });
+        slider.update_in(cx, |slider, window, _| window.focus(&slider.focus_handle));
+
+        cx.simulate_keystrokes("left home right pageup end right");
+        assert_eq!(*commits.borrow(), vec![1., 10.]);
+        assert_eq!(slider.read_with(cx, |slider, _| slider.value()), 10.);
+    }
+}
@@ -0,0 +1,898 @@
+use crate::text_input::{TextInput, TextInputEvent};
+use crate::theme::ThemeAble;
+use gpui::{
+    AnyElement, App, Bounds, Context, CursorStyle, DispatchPhase, Entity, EventEmitter,
+    FocusHandle, Focusable, KeyDownEvent, MouseButton, MouseDownEvent, MouseMoveEvent,
+    MouseUpEvent, Pixels, Point, ScrollWheelEvent, SharedString, Subscription, Window, actions,
+    canvas, div, prelude::*, px,
+};
+use std::cell::Cell;
+use std::ops::Range;
+use std::rc::Rc;
+
+const ROW_HEIGHT: Pixels = px(30.);
+const ROW_HEADER_WIDTH: Pixels = px(48.);
+const DEFAULT_COLUMN_WIDTH: Pixels = px(100.);
+const MIN_COLUMN_WIDTH: Pixels = px(24.);
+const RESIZE_HANDLE_WIDTH: Pixels = px(6.);
+
+actions!(
+    table,
+    [
+        MoveUp,
+        MoveDown,
+        MoveLeft,
+        MoveRight,
+        ExtendUp,
+        ExtendDown,
+        ExtendLeft,
+        ExtendRight,
+        StartEditing,
+        CancelEditing
+    ]
+);
+
+type RenderCell = Rc<dyn Fn(CellPosition, &mut Window, &mut App) -> AnyElement>;
+type CellText = Rc<dyn Fn(CellPosition, &App) -> String>;
+type RowHeader = Rc<dyn Fn(usize) -> SharedString>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CellPosition {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl CellPosition {
+    pub fn new(row: usize, column: usize) -> Self {
+        Self { row, column }
+    }
+}
+
+/// The cells between two corners, both included. `start` is the top-left
+/// corner and `end` the bottom-right one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellRange {
+    pub start: CellPosition,
+    pub end: CellPosition,
+}
+
+impl CellRange {
+    fn spanning(a: CellPosition, b: CellPosition) -> Self {
+        Self {
+            start: CellPosition::new(a.row.min(b.row), a.column.min(b.column)),
+            end: CellPosition::new(a.row.max(b.row), a.column.max(b.column)),
+        }
+    }
+
+    pub fn contains(&self, cell: CellPosition) -> bool {
+        (self.start.row..=self.end.row).contains(&cell.row)
+            && (self.start.column..=self.end.column).contains(&cell.column)
+    }
+}
+
+pub struct Column {
+    title: SharedString,
+    width: Pixels,
+}
+
+impl Column {
+    pub fn new(title: impl Into<SharedString>) -> Self {
+        Self {
+            title: title.into(),
+            width: DEFAULT_COLUMN_WIDTH,
+        }
+    }
+
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.width = width.max(MIN_COLUMN_WIDTH);
+        self
+    }
+}
+
+pub enum TableEvent {
+    /// The active cell or the selected range changed; read them with
+    /// [`Table::active_cell`] and [`Table::selection`].
+    SelectionChanged,
+    /// An edit was committed. The table doesn't store cell values, so the
+    /// owner should update its data and re-render.
+    CellEdited {
+        cell: CellPosition,
+        text: SharedString,
+    },
+}
+
+struct Editing {
+    cell: CellPosition,
+    input: Entity<TextInput>,
+    _subscription: Subscription,
+}
+
+struct Resizing {
+    column: usize,
+    start_x: Pixels,
+    start_width: Pixels,
+}
+
+/// A grid of `row_count` rows and the given columns, with a header row and a
+/// row header column that stay in place while the cells scroll. Only the
+/// cells in view are rendered, by calling `render_cell`.
+///
+/// Arrow keys move the active cell and extend the selection with Shift.
+/// Tables made [`editable`](Table::editable) edit the active cell in a
+/// [`TextInput`] on Enter, a double click or by typing. Columns are resized
+/// by dragging the right edge of their header.
+pub struct Table {
+    focus_handle: FocusHandle,
+    columns: Vec<Column>,
+    row_count: usize,
+    render_cell: RenderCell,
+    cell_text: Option<CellText>,
+    row_header: RowHeader,
+    anchor: Option<CellPosition>,
+    active: Option<CellPosition>,
+    selecting: bool,
+    editing: Option<Editing>,
+    resizing: Option<Resizing>,
+    scroll: Point<Pixels>,
+    body_bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
+}
+
+impl Table {
+    pub fn new(
+        columns: Vec<Column>,
+        row_count: usize,
+        render_cell: impl Fn(CellPosition, &mut Window, &mut App) -> AnyElement + 'static,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            columns,
+            row_count,
+            render_cell: Rc::new(render_cell),
+            cell_text: None,
+            row_header: Rc::new(|row| (row + 1).to_string().into()),
+            anchor: None,
+            active: None,
+            selecting: false,
+            editing: None,
+            resizing: None,
+            scroll: Point::default(),
+            body_bounds: Rc::default(),
+        }
+    }
+
+    /// Allows editing cells. `cell_text` gives the text an edit starts from,
+    /// e.g. a formula rather than its rendered result.
+    pub fn editable(mut self, cell_text: impl Fn(CellPosition, &App) -> String + 'static) -> Self {
+        self.cell_text = Some(Rc::new(cell_text));
+        self
+    }
+
+    /// Labels the row header cells. Defaults to row numbers counting from 1.
+    pub fn row_header(mut self, row_header: impl Fn(usize) -> SharedString + 'static) -> Self {
+        self.row_header = Rc::new(row_header);
+        self
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    /// Updates the number of rows after the data source changed. A selection
+    /// past the new end is cleared.
+    pub fn set_row_count(&mut self, row_count: usize, cx: &mut Context<Self>) {
+        self.row_count = row_count;
+        if self
+            .editing
+            .as_ref()
+            .is_some_and(|editing| editing.cell.row >= row_count)
+        {
+            self.editing = None;
+        }
+        let out_of_range = |cell: Option<CellPosition>| cell.is_some_and(|c| c.row >= row_count);
+        if out_of_range(self.active) || out_of_range(self.anchor) {
+            self.active = None;
+            self.anchor = None;
+            cx.emit(TableEvent::SelectionChanged);
+        }
+        self.clamp_scroll();
+        cx.notify();
+    }
+
+    pub fn column_width(&self, column: usize) -> Option<Pixels> {
+        self.columns.get(column).map(|column| column.width)
+    }
+
+    pub fn active_cell(&self) -> Option<CellPosition> {
+        self.active
+    }
+
+    /// The range from where the selection was started to the active cell.
+    pub fn selection(&self) -> Option<CellRange> {
+        Some(CellRange::spanning(self.anchor?, self.active?))
+    }
+
+    /// Makes `cell` the only selected cell, or clears the selection, and
+    /// scrolls it into view.
+    pub fn select(&mut self, cell: Option<CellPosition>, cx: &mut Context<Self>) {
+        let cell = cell.filter(|cell| self.contains(*cell));
+        self.set_selection(cell, cell, cx);
+    }
+
+    fn contains(&self, cell: CellPosition) -> bool {
+        cell.row < self.row_count && cell.column < self.columns.len()
+    }
+
+    fn set_selection(
+        &mut self,
+        anchor: Option<CellPosition>,
+        active: Option<CellPosition>,
+        cx: &mut Context<Self>,
+    ) {
+        let changed = (self.anchor, self.active) != (anchor, active);
+        self.anchor = anchor;
+        self.active = active;
+        if let Some(active) = active {
+            self.scroll_into_view(active);
+        }
+        if changed {
+            cx.emit(TableEvent::SelectionChanged);
+        }
+        cx.notify();
+    }
+
+    fn extend_to(&mut self, cell: CellPosition, cx: &mut Context<Self>) {
+        self.set_selection(self.anchor.or(Some(cell)), Some(cell), cx);
+    }
+
+    fn move_by(
+        &mut self,
+        rows: isize,
+        columns: isize,
+        extend: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.row_count == 0 || self.columns.is_empty() {
+            return;
+        }
+        self.commit_edit(window, cx);
+
+        let cell = match self.active {
+            Some(active) => CellPosition::new(
+                active
+                    .row
+                    .saturating_add_signed(rows)
+                    .min(self.row_count - 1),
+                active
+                    .column
+                    .saturating_add_signed(columns)
+                    .min(self.columns.len() - 1),
+            ),
+            None => CellPosition::new(0, 0),
+        };
+        if extend {
+            self.extend_to(cell, cx);
+        } else {
+            self.select(Some(cell), cx);
+        }
+    }
+
+    fn column_offsets(&self) -> Vec<Pixels> {
+        let mut offsets = Vec::with_capacity(self.columns.len() + 1);
+        let mut x = px(0.);
+        offsets.push(x);
+        for column in &self.columns {
+            x += column.width;
+            offsets.push(x);
+        }
+        offsets
+    }
+
+    // The size of the scrolling area, below and right of the headers.
+    fn viewport(&self) -> Option<(Pixels, Pixels)> {
+        let bounds = self.body_bounds.get()?;
+        Some((
+            (bounds.size.width - ROW_HEADER_WIDTH).max(px(0.)),
+            (bounds.size.height - ROW_HEIGHT).max(px(0.)),
+        ))
+    }
+
+    fn clamp_scroll(&mut self) {
+        let Some((width, height)) = self.viewport() else {
+            return;
+        };
+        let content_width = self.column_offsets().last().copied().unwrap_or_default();
+        let content_height = ROW_HEIGHT * self.row_count;
+        self.scroll.x = self
+            .scroll
+            .x
+            .min((content_width - width).max(px(0.)))
+            .max(px(0.));
+        self.scroll.y = self
+            .scroll
+            .y
+            .min((content_height - height).max(px(0.)))
+            .max(px(0.));
+    }
+
+    fn scroll_into_view(&mut self, cell: CellPosition) {
+        let Some((width, height)) = self.viewport() else {
+            return;
+        };
+        let offsets = self.column_offsets();
+        let (left, right) = (offsets[cell.column], offsets[cell.column + 1]);
+        let top = ROW_HEIGHT * cell.row;
+        let bottom = top + ROW_HEIGHT;
+
+        if left < self.scroll.x {
+            self.scroll.x = left;
+        } else if right > self.scroll.x + width {
+            self.scroll.x = (right - width).min(left);
+        }
+        if top < self.scroll.y {
+            self.scroll.y = top;
+        } else if bottom > self.scroll.y + height {
+            self.scroll.y = bottom - height;
+        }
+    }
+
+    fn start_editing(
+        &mut self,
+        cell: CellPosition,
+        text: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(cell_text) = self.cell_text.clone() else {
+            return;
+        };
+        let text = text.unwrap_or_else(|| cell_text(cell, cx));
+        let input = cx.new(|cx| {
+            let mut input = TextInput::new(window, cx);
+            input.set_content(text, cx);
+            input.move_to_end(cx);
+            input
+        });
+        let subscription =
+            cx.subscribe_in(&input, window, |this, _, event, window, cx| match event {
+                TextInputEvent::Submitted => this.move_by(1, 0, false, window, cx),
+                TextInputEvent::Blurred => {
+                    this.finish_edit(cx);
+                }
+                TextInputEvent::Changed { .. } | TextInputEvent::Focused => {}
+            });
+
+        window.focus(&input.focus_handle(cx));
+        self.editing = Some(Editing {
+            cell,
+            input,
+            _subscription: subscription,
+        });
+        self.scroll_into_view(cell);
+        cx.notify();
+    }
+
+    /// Ends an edit from inside the table, e.g. by moving to another cell,
+    /// and takes focus back from the input.
+    fn commit_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.finish_edit(cx) {
+            window.focus(&self.focus_handle);
+        }
+    }
+
+    /// Reports the edited text and removes the input, leaving focus where it
+    /// is. Returns whether an edit was in progress.
+    fn finish_edit(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(editing) = self.editing.take() else {
+            return false;
+        };
+        let text = editing.input.read(cx).content.clone();
+        cx.emit(TableEvent::CellEdited {
+            cell: editing.cell,
+            text,
+        });
+        cx.notify();
+        true
+    }
+
+    fn move_up(&mut self, _: &MoveUp, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(-1, 0, false, window, cx);
+    }
+
+    fn move_down(&mut self, _: &MoveDown, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(1, 0, false, window, cx);
+    }
+
+    fn move_left(&mut self, _: &MoveLeft, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(0, -1, false, window, cx);
+    }
+
+    fn move_right(&mut self, _: &MoveRight, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(0, 1, false, window, cx);
+    }
+
+    fn extend_up(&mut self, _: &ExtendUp, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(-1, 0, true, window, cx);
+    }
+
+    fn extend_down(&mut self, _: &ExtendDown, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(1, 0, true, window, cx);
+    }
+
+    fn extend_left(&mut self, _: &ExtendLeft, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(0, -1, true, window, cx);
+    }
+
+    fn extend_right(&mut self, _: &ExtendRight, window: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(0, 1, true, window, cx);
+    }
+
+    fn start_editing_action(
+        &mut self,
+        _: &StartEditing,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(cell) = self.active.filter(|_| self.editing.is_none()) {
+            self.start_editing(cell, None, window, cx);
+        }
+    }
+
+    fn cancel_editing(&mut self, _: &CancelEditing, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing.take().is_some() {
+            window.focus(&self.focus_handle);
+            cx.notify();
+        }
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let modifiers = &event.keystroke.modifiers;
+        if self.editing.is_some() || modifiers.control || modifiers.alt || modifiers.platform {
+            return;
+        }
+        let Some(cell) = self.active else {
+            return;
+        };
+        let Some(text) = event
+            .keystroke
+            .key_char
+            .clone()
+            .filter(|text| !text.chars().any(char::is_control))
+        else {
+            return;
+        };
+
+        // Typing over a cell replaces its content, like in a spreadsheet.
+        self.start_editing(cell, Some(text), window, cx);
+        cx.stop_propagation();
+    }
+
+    fn on_cell_mouse_down(
+        &mut self,
+        cell: CellPosition,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .editing
+            .as_ref()
+            .is_some_and(|editing| editing.cell == cell)
+        {
+            return;
+        }
+        self.commit_edit(window, cx);
+        window.focus(&self.focus_handle);
+
+        if event.click_count == 2 {
+            self.select(Some(cell), cx);
+            self.start_editing(cell, None, window, cx);
+        } else if event.modifiers.shift {
+            self.extend_to(cell, cx);
+            self.selecting = true;
+        } else {
+            self.select(Some(cell), cx);
+            self.selecting = true;
+        }
+    }
+
+    fn on_cell_mouse_move(
+        &mut self,
+        cell: CellPosition,
+        event: &MouseMoveEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selecting && event.dragging() && self.active != Some(cell) {
+            self.extend_to(cell, cx);
+        }
+    }
+
+    fn on_resize_move(&mut self, event: &MouseMoveEvent, cx: &mut Context<Self>) {
+        if let Some(resizing) = &self.resizing {
+            let width =
+                (resizing.start_width + event.position.x - resizing.start_x).max(MIN_COLUMN_WIDTH);
+            self.columns[resizing.column].width = width;
+            self.clamp_scroll();
+            cx.notify();
+        }
+    }
+
+    fn on_resize_end(&mut self, cx: &mut Context<Self>) {
+        if self.resizing.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut Window, cx: &mut Context<Self>) {
+        self.selecting = false;
+        self.on_resize_end(cx);
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(window.line_height());
+        self.scroll -= delta;
+        self.clamp_scroll();
+        cx.notify();
+    }
+
+    fn visible_rows(&self, height: Pixels) -> Range<usize> {
+        let first = (self.scroll.y / ROW_HEIGHT).floor() as usize;
+        let last = ((self.scroll.y + height) / ROW_HEIGHT).ceil() as usize;
+        first.min(self.row_count)..last.min(self.row_count)
+    }
+
+    fn visible_columns(&self, offsets: &[Pixels], width: Pixels) -> Range<usize> {
+        let first = offsets[1..].partition_point(|right| *right <= self.scroll.x);
+        let last =
+            offsets[..self.columns.len()].partition_point(|left| *left < self.scroll.x + width);
+        first..last.max(first)
+    }
+}
+
+impl Render for Table {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (width, height) = self.viewport().unwrap_or_else(|| {
+            let size = window.viewport_size();
+            (size.width, size.height)
+        });
+        let offsets = self.column_offsets();
+        let rows = self.visible_rows(height);
+        let columns = self.visible_columns(&offsets, width);
+        let cell_left = |column: usize| ROW_HEADER_WIDTH + offsets[column] - self.scroll.x;
+        let cell_top = |row: usize| ROW_HEIGHT + ROW_HEIGHT * row - self.scroll.y;
+
+        let render_cell = self.render_cell.clone();
+        let mut contents = Vec::with_capacity(rows.len() * columns.len());
+        for row in rows.clone() {
+            for column in columns.clone() {
+                let cell = CellPosition::new(row, column);
+                let content = match &self.editing {
+                    Some(editing) if editing.cell == cell => {
+                        editing.input.clone().into_any_element()
+                    }
+                    _ => render_cell(cell, window, cx),
+                };
+                contents.push((cell, content));
+            }
+        }
+
+        let focused = self.focus_handle.contains_focused(window, cx);
+        let selection = self.selection();
+        let editing_cell = self.editing.as_ref().map(|editing| editing.cell);
+        let theme = cx.theme();
+
+        let cells = contents.into_iter().map(|(cell, content)| {
+            let editing = editing_cell == Some(cell);
+            div()
+                .absolute()
+                .left(cell_left(cell.column))
+                .top(cell_top(cell.row))
+                .w(self.columns[cell.column].width)
+                .h(ROW_HEIGHT)
+                .flex()
+                .items_center()
+                .overflow_hidden()
+                .border_r_1()
+                .border_b_1()
+                .border_color(theme.border)
+                .when(!editing, |this| this.px(theme.spacing.sm))
+                .when(
+                    selection.is_some_and(|selection| selection.contains(cell)),
+                    |this| this.bg(theme.selection),
+                )
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(move |this, event, window, cx| {
+                        this.on_cell_mouse_down(cell, event, window, cx)
+                    }),
+                )
+                .on_mouse_move(
+                    cx.listener(move |this, event, _, cx| this.on_cell_mouse_move(cell, event, cx)),
+                )
+                .child(content)
+        });
+
+        let active = self.active.filter(|_| editing_cell.is_none()).map(|cell| {
+            div()
+                .absolute()
+                .left(cell_left(cell.column))
+                .top(cell_top(cell.row))
+                .w(self.columns[cell.column].width)
+                .h(ROW_HEIGHT)
+                .border_2()
+                .border_color(if focused {
+                    theme.focus_ring
+                } else {
+                    theme.muted_foreground
+                })
+        });
+
+        let header = |label: SharedString, selected: bool| {
+            div()
+                .absolute()
+                .h(ROW_HEIGHT)
+                .flex()
+                .items_center()
+                .justify_center()
+                .overflow_hidden()
+                .border_r_1()
+                .border_b_1()
+                .border_color(theme.border)
+                .bg(if selected {
+                    theme.highlight
+                } else {
+                    theme.ground
+                })
+                .text_color(theme.muted_foreground)
+                // Cells scrolled under the headers mustn't react to the mouse.
+                .block_mouse_except_scroll()
+                .child(label)
+        };
+
+        let row_headers = rows.map(|row| {
+            let selected = selection
+                .is_some_and(|selection| (selection.start.row..=selection.end.row).contains(&row));
+            header((self.row_header)(row), selected)
+                .left(px(0.))
+                .top(cell_top(row))
+                .w(ROW_HEADER_WIDTH)
+        });
+
+        let column_headers = columns.map(|column| {
+            let selected = selection.is_some_and(|selection| {
+                (selection.start.column..=selection.end.column).contains(&column)
+            });
+            header(self.columns[column].title.clone(), selected)
+                .left(cell_left(column))
+                .top(px(0.))
+                .w(self.columns[column].width)
+                .child(
+                    div()
+                        .absolute()
+                        .top(px(0.))
+                        .right(px(0.))
+                        .h_full()
+                        .w(RESIZE_HANDLE_WIDTH)
+                        .cursor(CursorStyle::ResizeLeftRight)
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(move |this, event: &MouseDownEvent, _, cx| {
+                                this.resizing = Some(Resizing {
+                                    column,
+                                    start_x: event.position.x,
+                                    start_width: this.columns[column].width,
+                                });
+                                cx.stop_propagation();
+                                cx.notify();
+                            }),
+                        ),
+                )
+        });
+
+        let corner = header(SharedString::default(), false)
+            .left(px(0.))
+            .top(px(0.))
+            .w(ROW_HEADER_WIDTH);
+
+        let body = div()
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .children(cells)
+            .children(active)
+            .children(row_headers)
+            .children(column_headers)
+            .child(corner)
+            .when(self.resizing.is_some(), |this| {
+                // Follow a resize drag anywhere in the window, not just while
+                // the pointer is over the table.
+                let entity = cx.entity();
+                this.child(
+                    canvas(
+                        |_, _, _| {},
+                        move |_, _, window, _| {
+                            let table = entity.clone();
+                            window.on_mouse_event(move |event: &MouseMoveEvent, phase, _, cx| {
+                                if phase == DispatchPhase::Bubble {
+                                    table.update(cx, |table, cx| table.on_resize_move(event, cx));
+                                }
+                            });
+                            let table = entity;
+                            window.on_mouse_event(move |event: &MouseUpEvent, phase, _, cx| {
+                                if phase == DispatchPhase::Bubble
+                                    && event.button == MouseButton::Left
+                                {
+                                    table.update(cx, |table, cx| table.on_resize_end(cx));
+                                }
+                            });
+                        },
+                    )
+                    .absolute()
+                    .size_full(),
+                )
+            });
+
+        div()
+            .on_children_prepainted({
+                let body_bounds = self.body_bounds.clone();
+                move |bounds, _, _| body_bounds.set(bounds.first().copied())
+            })
+            .size_full()
+            .border_1()
+            .border_color(if focused {
+                theme.focus_ring
+            } else {
+                theme.border
+            })
+            .rounded(theme.radius.md)
+            .overflow_hidden()
+            .bg(theme.surface)
+            .text_size(theme.text.sm)
+            .key_context("Table")
+            .track_focus(&self.focus_handle)
+            .when(self.resizing.is_some(), |this| {
+                this.cursor(CursorStyle::ResizeLeftRight)
+            })
+            .on_action(cx.listener(Self::move_up))
+            .on_action(cx.listener(Self::move_down))
+            .on_action(cx.listener(Self::move_left))
+            .on_action(cx.listener(Self::move_right))
+            .on_action(cx.listener(Self::extend_up))
+            .on_action(cx.listener(Self::extend_down))
+            .on_action(cx.listener(Self::extend_left))
+            .on_action(cx.listener(Self::extend_right))
+            .on_action(cx.listener(Self::start_editing_action))
+            .on_action(cx.listener(Self::cancel_editing))
+            .on_key_down(cx.listener(Self::on_key_down))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+            .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .child(body)
+    }
+}
+
+impl EventEmitter<TableEvent> for Table {}
+
+impl Focusable for Table {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext, point};
+    use std::cell::RefCell;
+
+    fn table(cx: &mut TestAppContext) -> (Entity<Table>, &mut VisualTestContext) {
+        cx.update(crate::init);
+        let (table, cx) = cx.add_window_view(|_, cx| {
+            let columns = vec![Column::new("A"), Column::new("B"), Column::new("C")];
+            Table::new(columns, 10, |_, _, _| div().into_any_element(), cx)
+                .editable(|cell, _| format!("{}{}", cell.row, cell.column))
+        });
+        table.update_in(cx, |table, window, _| window.focus(&table.focus_handle));
+        (table, cx)
+    }
+
+    #[test]
+    fn ranges_span_any_two_corners() {
+        let range = CellRange::spanning(CellPosition::new(4, 1), CellPosition::new(2, 3));
+        assert_eq!(range.start, CellPosition::new(2, 1));
+        assert_eq!(range.end, CellPosition::new(4, 3));
+        assert!(range.contains(CellPosition::new(3, 2)));
+        assert!(range.contains(CellPosition::new(4, 1)));
+        assert!(!range.contains(CellPosition::new(1, 2)));
+        assert!(!range.contains(CellPosition::new(3, 4)));
+    }
+
+    #[gpui::test]
+    fn arrows_move_and_shift_extends(cx: &mut TestAppContext) {
+        let (table, cx) = table(cx);
+
+        cx.simulate_keystrokes("down down right shift-up shift-right");
+        let (active, selection) =
+            table.read_with(cx, |table, _| (table.active_cell(), table.selection()));
+        assert_eq!(active, Some(CellPosition::new(0, 2)));
+        assert_eq!(
+            selection,
+            Some(CellRange {
+                start: CellPosition::new(0, 1),
+                end: CellPosition::new(1, 2),
+            })
+        );
+
+        cx.simulate_keystrokes("left");
+        let (active, selection) =
+            table.read_with(cx, |table, _| (table.active_cell(), table.selection()));
+        assert_eq!(active, Some(CellPosition::new(0, 1)));
+        assert_eq!(
+            selection,
+            Some(CellRange::spanning(active.unwrap(), active.unwrap()))
+        );
+        assert_eq!(table.read_with(cx, |table, _| table.column_width(3)), None);
+    }
+
+    #[gpui::test]
+    fn edits_commit_on_enter_and_cancel_on_escape(cx: &mut TestAppContext) {
+        let (table, cx) = table(cx);
+        let edits = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|_, cx| {
+            let edits = edits.clone();
+            cx.subscribe(&table, move |_, event, _| {
+                if let TableEvent::CellEdited { cell, text } = event {
+                    edits.borrow_mut().push((*cell, text.to_string()));
+                }
+            })
+            .detach();
+        });
+
+        cx.simulate_keystrokes("down enter");
+        cx.simulate_input("!");
+        cx.simulate_keystrokes("enter");
+        assert_eq!(
+            *edits.borrow(),
+            vec![(CellPosition::new(0, 0), "00!".into())]
+        );
+        assert_eq!(
+            table.read_with(cx, |table, _| table.active_cell()),
+            Some(CellPosition::new(1, 0))
+        );
+
+        cx.simulate_keystrokes("enter");
+        cx.simulate_input("?");
+        cx.simulate_keystrokes("escape");
+        assert_eq!(edits.borrow().len(), 1);
+        assert!(table.read_with(cx, |table, _| table.editing.is_none()));
+    }
+
+    #[gpui::test]
+    fn resizing_stops_at_the_minimum_width(cx: &mut TestAppContext) {
+        let (table, cx) = table(cx);
+        table.update(cx, |table, cx| {
+            table.resizing = Some(Resizing {
+                column: 1,
+                start_x: px(200.),
+                start_width: DEFAULT_COLUMN_WIDTH,
+            });
+            let moved_to = |x| MouseMoveEvent {
+                position: point(px(x), px(0.)),
+                ..Default::default()
+            };
+
+            table.on_resize_move(&moved_to(250.), cx);
+            assert_eq!(table.column_width(1), Some(DEFAULT_COLUMN_WIDTH + px(50.)));
+            table.on_resize_move(&moved_to(0.), cx);
+            assert_eq!(table.column_width(1), Some(MIN_COLUMN_WIDTH));
+            table.on_resize_end(cx);
+            assert!(table.resizing.is_none());
+        });
+    }
+}
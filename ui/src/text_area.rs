@@ -0,0 +1,922 @@
+use crate::history::{History, Snapshot};
+use crate::text_utils;
+use crate::theme::ThemeAble;
+use crate::{
+    Backspace, Copy, Cut, Delete, End, Home, Left, Paste, Redo, Right, SelectAll, SelectLeft,
+    SelectRight, ShowCharacterPalette, Undo,
+};
+use gpui::{
+    App, Bounds, ClipboardItem, ContentMask, Context, CursorStyle, ElementId, ElementInputHandler,
+    Entity, EntityInputHandler, EventEmitter, FocusHandle, Focusable, GlobalElementId, LayoutId,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, Pixels, Point,
+    ScrollWheelEvent, SharedString, Style, Styled, Subscription, TextAlign, TextRun,
+    UTF16Selection, UnderlineStyle, Window, WrappedLine, actions, div, fill, point, prelude::*, px,
+    relative, size,
+};
+use std::ops::Range;
+
+actions!(
+    text_area,
+    [Up, Down, SelectUp, SelectDown, PageUp, PageDown, Newline]
+);
+
+/// The same events as [`TextInputEvent`](crate::TextInputEvent), except that
+/// Enter inserts a newline instead of submitting.
+pub enum TextAreaEvent {
+    Changed {
+        old: SharedString,
+        new: SharedString,
+    },
+    Focused,
+    Blurred,
+}
+
+pub struct TextArea {
+    pub focus_handle: FocusHandle,
+    pub content: SharedString,
+    pub placeholder: SharedString,
+    pub selected_range: Range<usize>,
+    pub selection_reversed: bool,
+    pub marked_range: Option<Range<usize>>,
+    pub last_bounds: Option<Bounds<Pixels>>,
+    pub is_selecting: bool,
+    pub disabled: bool,
+    rows: usize,
+    scroll_top: Pixels,
+    scroll_to_cursor: bool,
+    goal_x: Option<Pixels>,
+    last_layout: Option<TextAreaLayout>,
+    history: History,
+    _focus_subscriptions: Vec<Subscription>,
+}
+
+impl TextArea {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        let focus_subscriptions = vec![
+            cx.on_focus(&focus_handle, window, |_, _, cx| {
+                cx.emit(TextAreaEvent::Focused)
+            }),
+            cx.on_blur(&focus_handle, window, |_, _, cx| {
+                cx.emit(TextAreaEvent::Blurred)
+            }),
+        ];
+
+        Self {
+            focus_handle,
+            content: "".into(),
+            placeholder: "".into(),
+            selected_range: 0..0,
+            selection_reversed: false,
+            marked_range: None,
+            last_bounds: None,
+            is_selecting: false,
+            disabled: false,
+            rows: 4,
+            scroll_top: px(0.),
+            scroll_to_cursor: false,
+            goal_x: None,
+            last_layout: None,
+            history: History::default(),
+            _focus_subscriptions: focus_subscriptions,
+        }
+    }
+
+    pub fn disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+    }
+
+    pub fn placeholder(mut self, placeholder: SharedString) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+
+    fn left(&mut self, _: &Left, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_range.is_empty() {
+            self.move_to(self.previous_boundary(self.cursor_offset()), cx);
+        } else {
+            self.move_to(self.selected_range.start, cx)
+        }
+    }
+
+    fn right(&mut self, _: &Right, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_range.is_empty() {
+            self.move_to(self.next_boundary(self.selected_range.end), cx);
+        } else {
+            self.move_to(self.selected_range.end, cx)
+        }
+    }
+
+    fn up(&mut self, _: &Up, _: &mut Window, cx: &mut Context<Self>) {
+        let (offset, goal_x) = self.vertical_target(-1);
+        self.move_to(offset, cx);
+        self.goal_x = goal_x;
+    }
+
+    fn down(&mut self, _: &Down, _: &mut Window, cx: &mut Context<Self>) {
+        let (offset, goal_x) = self.vertical_target(1);
+        self.move_to(offset, cx);
+        self.goal_x = goal_x;
+    }
+
+    fn page_up(&mut self, _: &PageUp, _: &mut Window, cx: &mut Context<Self>) {
+        let (offset, goal_x) = self.vertical_target(-(self.rows as isize));
+        self.move_to(offset, cx);
+        self.goal_x = goal_x;
+    }
+
+    fn page_down(&mut self, _: &PageDown, _: &mut Window, cx: &mut Context<Self>) {
+        let (offset, goal_x) = self.vertical_target(self.rows as isize);
+        self.move_to(offset, cx);
+        self.goal_x = goal_x;
+    }
+
+    fn select_left(&mut self, _: &SelectLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.previous_boundary(self.cursor_offset()), cx);
+    }
+
+    fn select_right(&mut self, _: &SelectRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.next_boundary(self.cursor_offset()), cx);
+    }
+
+    fn select_up(&mut self, _: &SelectUp, _: &mut Window, cx: &mut Context<Self>) {
+        let (offset, goal_x) = self.vertical_target(-1);
+        self.select_to(offset, cx);
+        self.goal_x = goal_x;
+    }
+
+    fn select_down(&mut self, _: &SelectDown, _: &mut Window, cx: &mut Context<Self>) {
+        let (offset, goal_x) = self.vertical_target(1);
+        self.select_to(offset, cx);
+        self.goal_x = goal_x;
+    }
+
+    fn select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(0, cx);
+        self.select_to(self.content.len(), cx)
+    }
+
+    fn home(&mut self, _: &Home, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.line_start(self.cursor_offset()), cx);
+    }
+
+    fn end(&mut self, _: &End, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.line_end(self.cursor_offset()), cx);
+    }
+
+    fn backspace(&mut self, _: &Backspace, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.previous_boundary(self.cursor_offset()), cx)
+        }
+        self.replace_text_in_range(None, "", window, cx)
+    }
+
+    fn delete(&mut self, _: &Delete, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.next_boundary(self.cursor_offset()), cx)
+        }
+        self.replace_text_in_range(None, "", window, cx)
+    }
+
+    fn newline(&mut self, _: &Newline, window: &mut Window, cx: &mut Context<Self>) {
+        self.replace_text_in_range(None, "\n", window, cx)
+    }
+
+    fn on_mouse_down(
+        &mut self,
+        event: &MouseDownEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.is_selecting = true;
+
+        if event.modifiers.shift {
+            self.select_to(self.index_for_mouse_position(event.position), cx);
+        } else {
+            self.move_to(self.index_for_mouse_position(event.position), cx)
+        }
+    }
+
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _window: &mut Window, _: &mut Context<Self>) {
+        self.is_selecting = false;
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.is_selecting {
+            self.select_to(self.index_for_mouse_position(event.position), cx);
+        }
+    }
+
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(window.line_height());
+        self.scroll_top = (self.scroll_top - delta.y).max(px(0.));
+        cx.notify()
+    }
+
+    fn show_character_palette(
+        &mut self,
+        _: &ShowCharacterPalette,
+        window: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+        window.show_character_palette();
+    }
+
+    fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+            self.replace_text_in_range(None, &text.replace("\r\n", "\n"), window, cx);
+        }
+    }
+
+    fn copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.selected_range.is_empty() {
+            cx.write_to_clipboard(ClipboardItem::new_string(
+                self.content[self.selected_range.clone()].to_string(),
+            ));
+        }
+    }
+
+    fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.selected_range.is_empty() {
+            cx.write_to_clipboard(ClipboardItem::new_string(
+                self.content[self.selected_range.clone()].to_string(),
+            ));
+            self.replace_text_in_range(None, "", window, cx)
+        }
+    }
+
+    fn undo(&mut self, _: &Undo, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(snapshot) = self.history.undo(self.snapshot()) {
+            self.restore(snapshot, cx);
+        }
+    }
+
+    fn redo(&mut self, _: &Redo, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(snapshot) = self.history.redo(self.snapshot()) {
+            self.restore(snapshot, cx);
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            content: self.content.clone(),
+            selected_range: self.selected_range.clone(),
+            selection_reversed: self.selection_reversed,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot, cx: &mut Context<Self>) {
+        let old = std::mem::replace(&mut self.content, snapshot.content);
+        self.selected_range = snapshot.selected_range;
+        self.selection_reversed = snapshot.selection_reversed;
+        self.marked_range = None;
+        self.scroll_to_cursor = true;
+        self.emit_changed(old, cx);
+        cx.notify()
+    }
+
+    fn emit_changed(&self, old: SharedString, cx: &mut Context<Self>) {
+        if old != self.content {
+            cx.emit(TextAreaEvent::Changed {
+                old,
+                new: self.content.clone(),
+            });
+        }
+    }
+
+    fn move_to(&mut self, offset: usize, cx: &mut Context<Self>) {
+        self.selected_range = offset..offset;
+        self.goal_x = None;
+        self.scroll_to_cursor = true;
+        self.history.break_group();
+        cx.notify()
+    }
+
+    fn select_to(&mut self, offset: usize, cx: &mut Context<Self>) {
+        if self.selection_reversed {
+            self.selected_range.start = offset
+        } else {
+            self.selected_range.end = offset
+        };
+        if self.selected_range.end < self.selected_range.start {
+            self.selection_reversed = !self.selection_reversed;
+            self.selected_range = self.selected_range.end..self.selected_range.start;
+        }
+        self.goal_x = None;
+        self.scroll_to_cursor = true;
+        cx.notify()
+    }
+
+    fn cursor_offset(&self) -> usize {
+        if self.selection_reversed {
+            self.selected_range.start
+        } else {
+            self.selected_range.end
+        }
+    }
+
+    /// The offset `rows` visual rows above or below the cursor, keeping the
+    /// horizontal position the cursor had when vertical movement started.
+    fn vertical_target(&self, rows: isize) -> (usize, Option<Pixels>) {
+        let cursor = self.cursor_offset();
+        let Some(layout) = self.last_layout.as_ref() else {
+            return (cursor, None);
+        };
+
+        let position = layout.position_for_index(cursor);
+        let goal_x = self.goal_x.unwrap_or(position.x);
+        let y = position.y + layout.line_height * (rows as f32 + 0.5);
+        let offset = if y < px(0.) {
+            0
+        } else if y >= layout.height {
+            self.content.len()
+        } else {
+            layout.index_for_position(point(goal_x, y))
+        };
+
+        (offset, Some(goal_x))
+    }
+
+    fn index_for_mouse_position(&self, position: Point<Pixels>) -> usize {
+        if self.content.is_empty() {
+            return 0;
+        }
+
+        let (Some(bounds), Some(layout)) = (self.last_bounds.as_ref(), self.last_layout.as_ref())
+        else {
+            return 0;
+        };
+        layout.index_for_position(point(
+            position.x - bounds.left(),
+            position.y - bounds.top() + self.scroll_top,
+        ))
+    }
+
+    fn line_start(&self, offset: usize) -> usize {
+        self.content[..offset].rfind('\n').map_or(0, |ix| ix + 1)
+    }
+
+    fn line_end(&self, offset: usize) -> usize {
+        self.content[offset..]
+            .find('\n')
+            .map_or(self.content.len(), |ix| offset + ix)
+    }
+
+    fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
+        text_utils::range_to_utf16(&self.content, range)
+    }
+
+    fn range_from_utf16(&self, range_utf16: &Range<usize>) -> Range<usize> {
+        text_utils::range_from_utf16(&self.content, range_utf16)
+    }
+
+    fn previous_boundary(&self, offset: usize) -> usize {
+        text_utils::previous_boundary(&self.content, offset)
+    }
+
+    fn next_boundary(&self, offset: usize) -> usize {
+        text_utils::next_boundary(&self.content, offset)
+    }
+
+    /// Replaces the content, clearing the selection and undo history, and
+    /// emits [`TextAreaEvent::Changed`] if it differs.
+    pub fn set_content(&mut self, new_content: String, cx: &mut Context<Self>) {
+        let old = std::mem::replace(&mut self.content, new_content.into());
+        self.selected_range = 0..0;
+        self.selection_reversed = false;
+        self.marked_range = None;
+        self.last_layout = None;
+        self.last_bounds = None;
+        self.is_selecting = false;
+        self.scroll_top = px(0.);
+        self.goal_x = None;
+        self.history.clear();
+        self.emit_changed(old, cx);
+        cx.notify()
+    }
+
+    pub fn reset(&mut self, cx: &mut Context<Self>) {
+        self.set_content(String::new(), cx);
+    }
+}
+
+impl EntityInputHandler for TextArea {
+    fn text_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        actual_range: &mut Option<Range<usize>>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<String> {
+        let range = self.range_from_utf16(&range_utf16);
+        actual_range.replace(self.range_to_utf16(&range));
+        Some(self.content[range].to_string())
+    }
+
+    fn selected_text_range(
+        &mut self,
+        _ignore_disabled_input: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<UTF16Selection> {
+        Some(UTF16Selection {
+            range: self.range_to_utf16(&self.selected_range),
+            reversed: self.selection_reversed,
+        })
+    }
+
+    fn marked_text_range(
+        &self,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<Range<usize>> {
+        self.marked_range
+            .as_ref()
+            .map(|range| self.range_to_utf16(range))
+    }
+
+    fn unmark_text(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
+        self.marked_range = None;
+    }
+
+    fn replace_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let range = range_utf16
+            .as_ref()
+            .map(|range_utf16| self.range_from_utf16(range_utf16))
+            .or(self.marked_range.clone())
+            .unwrap_or(self.selected_range.clone());
+
+        // A composition was recorded as a whole when it started.
+        if self.marked_range.is_none() {
+            self.history.record(self.snapshot(), &range, new_text);
+        }
+
+        let new_content =
+            self.content[0..range.start].to_owned() + new_text + &self.content[range.end..];
+        let old = std::mem::replace(&mut self.content, new_content.into());
+        self.selected_range = range.start + new_text.len()..range.start + new_text.len();
+        self.marked_range.take();
+        self.goal_x = None;
+        self.scroll_to_cursor = true;
+        self.emit_changed(old, cx);
+        cx.notify();
+    }
+
+    fn replace_and_mark_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        new_selected_range_utf16: Option<Range<usize>>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let range = range_utf16
+            .as_ref()
+            .map(|range_utf16| self.range_from_utf16(range_utf16))
+            .or(self.marked_range.clone())
+            .unwrap_or(self.selected_range.clone());
+
+        if self.marked_range.is_none() {
+            self.history.record(self.snapshot(), &range, new_text);
+            self.history.break_group();
+        }
+
+        let new_content =
+            self.content[0..range.start].to_owned() + new_text + &self.content[range.end..];
+        let old = std::mem::replace(&mut self.content, new_content.into());
+        if !new_text.is_empty() {
+            self.marked_range = Some(range.start..range.start + new_text.len());
+        } else {
+            self.marked_range = None;
+        }
+        self.selected_range = new_selected_range_utf16
+            .as_ref()
+            .map(|range_utf16| self.range_from_utf16(range_utf16))
+            .map(|new_range| new_range.start + range.start..new_range.end + range.end)
+            .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
+        self.goal_x = None;
+        self.scroll_to_cursor = true;
+        self.emit_changed(old, cx);
+
+        cx.notify();
+    }
+
+    fn bounds_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        bounds: Bounds<Pixels>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<Bounds<Pixels>> {
+        let layout = self.last_layout.as_ref()?;
+        let range = self.range_from_utf16(&range_utf16);
+        let origin = point(bounds.left(), bounds.top() - self.scroll_top);
+        let start = layout.position_for_index(range.start);
+        let end = layout.position_for_index(range.end);
+        Some(Bounds::from_corners(
+            origin + start,
+            origin + point(end.x, end.y + layout.line_height),
+        ))
+    }
+
+    fn character_index_for_point(
+        &mut self,
+        point: gpui::Point<Pixels>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<usize> {
+        let local_point = self.last_bounds?.localize(&point)?;
+        let layout = self.last_layout.as_ref()?;
+        let utf8_index =
+            layout.index_for_position(gpui::point(local_point.x, local_point.y + self.scroll_top));
+        Some(text_utils::offset_to_utf16(&self.content, utf8_index))
+    }
+}
+
+struct LayoutLine {
+    start: usize,
+    top: Pixels,
+    line: WrappedLine,
+}
+
+/// The wrapped lines of a text area, one per `\n`-separated line of content,
+/// positioned in content coordinates (before scrolling).
+struct TextAreaLayout {
+    lines: Vec<LayoutLine>,
+    line_height: Pixels,
+    height: Pixels,
+}
+
+impl TextAreaLayout {
+    // `None` only if shaping failed and there are no lines at all.
+    fn line_for_index(&self, index: usize) -> Option<&LayoutLine> {
+        self.lines
+            .iter()
+            .rev()
+            .find(|line| line.start <= index)
+            .or(self.lines.first())
+    }
+
+    fn position_for_index(&self, index: usize) -> Point<Pixels> {
+        let Some(line) = self.line_for_index(index) else {
+            return Point::default();
+        };
+        let position = line
+            .line
+            .position_for_index(index - line.start, self.line_height)
+            .unwrap_or_default();
+        point(position.x, line.top + position.y)
+    }
+
+    fn index_for_position(&self, position: Point<Pixels>) -> usize {
+        let Some(line) = self
+            .lines
+            .iter()
+            .rev()
+            .find(|line| line.top <= position.y)
+            .or(self.lines.first())
+        else {
+            return 0;
+        };
+        let height = line.line.size(self.line_height).height;
+        let local = point(
+            position.x.max(px(0.)),
+            (position.y - line.top).clamp(px(0.), height - px(1.)),
+        );
+        match line
+            .line
+            .closest_index_for_position(local, self.line_height)
+        {
+            Ok(ix) | Err(ix) => line.start + ix,
+        }
+    }
+}
+
+struct TextAreaElement {
+    input: Entity<TextArea>,
+}
+
+struct PrepaintState {
+    layout: Option<TextAreaLayout>,
+    scroll_top: Pixels,
+    cursor: Option<PaintQuad>,
+    selections: Vec<PaintQuad>,
+}
+
+impl IntoElement for TextAreaElement {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for TextAreaElement {
+    type RequestLayoutState = ();
+    type PrepaintState = PrepaintState;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&gpui::InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let rows = self.input.read(cx).rows;
+        let mut style = Style::default();
+        style.size.width = relative(1.).into();
+        style.size.height = (window.line_height() * rows).into();
+        (window.request_layout(style, [], cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&gpui::InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        let input = self.input.read(cx);
+        let content = input.content.clone();
+        let selected_range = input.selected_range.clone();
+        let cursor = input.cursor_offset();
+        let style = window.text_style();
+        let line_height = window.line_height();
+        let theme = cx.theme();
+
+        let (display_text, text_color) = if content.is_empty() {
+            (input.placeholder.clone(), theme.placeholder)
+        } else {
+            (content, style.color)
+        };
+
+        let run = TextRun {
+            len: display_text.len(),
+            font: style.font(),
+            color: text_color,
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+        };
+        let runs = if let Some(marked_range) = input.marked_range.as_ref() {
+            vec![
+                TextRun {
+                    len: marked_range.start,
+                    ..run.clone()
+                },
+                TextRun {
+                    len: marked_range.end - marked_range.start,
+                    underline: Some(UnderlineStyle {
+                        color: Some(run.color),
+                        thickness: px(1.0),
+                        wavy: false,
+                    }),
+                    ..run.clone()
+                },
+                TextRun {
+                    len: display_text.len() - marked_range.end,
+                    ..run
+                },
+            ]
+            .into_iter()
+            .filter(|run| run.len > 0)
+            .collect()
+        } else {
+            vec![run]
+        };
+
+        let font_size = style.font_size.to_pixels(window.rem_size());
+        let wrapped_lines = window
+            .text_system()
+            .shape_text(
+                display_text,
+                font_size,
+                &runs,
+                Some(bounds.size.width),
+                None,
+            )
+            .unwrap_or_default();
+
+        let mut lines = Vec::with_capacity(wrapped_lines.len());
+        let mut start = 0;
+        let mut top = px(0.);
+        for line in wrapped_lines {
+            let len = line.len();
+            let height = line.size(line_height).height;
+            lines.push(LayoutLine { start, top, line });
+            start += len + 1;
+            top += height;
+        }
+        let layout = TextAreaLayout {
+            lines,
+            line_height,
+            height: top,
+        };
+
+        let cursor_pos = layout.position_for_index(cursor);
+        let max_scroll = (layout.height - bounds.size.height).max(px(0.));
+        let mut scroll_top = input.scroll_top.min(max_scroll);
+        if input.scroll_to_cursor {
+            if cursor_pos.y < scroll_top {
+                scroll_top = cursor_pos.y;
+            } else if cursor_pos.y + line_height > scroll_top + bounds.size.height {
+                scroll_top = cursor_pos.y + line_height - bounds.size.height;
+            }
+        }
+
+        let origin = point(bounds.left(), bounds.top() - scroll_top);
+        let row = |y: Pixels, x: Range<Pixels>| {
+            fill(
+                Bounds::from_corners(
+                    origin + point(x.start, y),
+                    origin + point(x.end, y + line_height),
+                ),
+                theme.selection,
+            )
+        };
+
+        let (selections, cursor) = if selected_range.is_empty() {
+            (
+                Vec::new(),
+                Some(fill(
+                    Bounds::new(origin + cursor_pos, size(px(2.), line_height)),
+                    theme.caret,
+                )),
+            )
+        } else {
+            let start = layout.position_for_index(selected_range.start);
+            let end = layout.position_for_index(selected_range.end);
+            let mut selections = Vec::new();
+            if start.y == end.y {
+                selections.push(row(start.y, start.x..end.x));
+            } else {
+                selections.push(row(start.y, start.x..bounds.size.width));
+                let mut y = start.y + line_height;
+                while y < end.y {
+                    selections.push(row(y, px(0.)..bounds.size.width));
+                    y += line_height;
+                }
+                selections.push(row(end.y, px(0.)..end.x));
+            }
+            (selections, None)
+        };
+
+        PrepaintState {
+            layout: Some(layout),
+            scroll_top,
+            cursor,
+            selections,
+        }
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&gpui::InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let focus_handle = self.input.read(cx).focus_handle.clone();
+        window.handle_input(
+            &focus_handle,
+            ElementInputHandler::new(bounds, self.input.clone()),
+            cx,
+        );
+
+        let layout = prepaint.layout.take().unwrap();
+        let scroll_top = prepaint.scroll_top;
+        window.with_content_mask(Some(ContentMask { bounds }), |window| {
+            for selection in prepaint.selections.drain(..) {
+                window.paint_quad(selection)
+            }
+
+            for line in &layout.lines {
+                let top = bounds.top() + line.top - scroll_top;
+                let height = line.line.size(layout.line_height).height;
+                if top + height < bounds.top() || top > bounds.bottom() {
+                    continue;
+                }
+                line.line
+                    .paint(
+                        point(bounds.left(), top),
+                        layout.line_height,
+                        TextAlign::Left,
+                        None,
+                        window,
+                        cx,
+                    )
+                    .unwrap();
+            }
+
+            if focus_handle.is_focused(window)
+                && let Some(cursor) = prepaint.cursor.take()
+            {
+                window.paint_quad(cursor);
+            }
+        });
+
+        self.input.update(cx, |input, _cx| {
+            input.last_layout = Some(layout);
+            input.last_bounds = Some(bounds);
+            input.scroll_top = scroll_top;
+            input.scroll_to_cursor = false;
+        });
+    }
+}
+
+impl Render for TextArea {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focused = self.focus_handle.is_focused(window);
+        let theme = cx.theme();
+
+        div()
+            .flex()
+            .px(theme.spacing.md)
+            .py(theme.spacing.sm)
+            .w_full()
+            .border_1()
+            .border_color(if focused {
+                theme.focus_ring
+            } else {
+                theme.border
+            })
+            .rounded(theme.radius.md)
+            .overflow_hidden()
+            .key_context("TextArea")
+            .track_focus(&self.focus_handle(cx))
+            .cursor(CursorStyle::IBeam)
+            .line_height(theme.text.line_height)
+            .text_size(theme.text.md)
+            .when(self.disabled, |this| {
+                this.cursor(CursorStyle::OperationNotAllowed).opacity(0.6)
+            })
+            .when(!self.disabled, |this| {
+                this.on_action(cx.listener(Self::backspace))
+                    .on_action(cx.listener(Self::delete))
+                    .on_action(cx.listener(Self::newline))
+                    .on_action(cx.listener(Self::left))
+                    .on_action(cx.listener(Self::right))
+                    .on_action(cx.listener(Self::up))
+                    .on_action(cx.listener(Self::down))
+                    .on_action(cx.listener(Self::page_up))
+                    .on_action(cx.listener(Self::page_down))
+                    .on_action(cx.listener(Self::select_left))
+                    .on_action(cx.listener(Self::select_right))
+                    .on_action(cx.listener(Self::select_up))
+                    .on_action(cx.listener(Self::select_down))
+                    .on_action(cx.listener(Self::select_all))
+                    .on_action(cx.listener(Self::home))
+                    .on_action(cx.listener(Self::end))
+                    .on_action(cx.listener(Self::show_character_palette))
+                    .on_action(cx.listener(Self::paste))
+                    .on_action(cx.listener(Self::cut))
+                    .on_action(cx.listener(Self::copy))
+                    .on_action(cx.listener(Self::undo))
+                    .on_action(cx.listener(Self::redo))
+                    .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+                    .on_mouse_move(cx.listener(Self::on_mouse_move))
+                    .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+                    .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
+                    .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
+                    .bg(theme.transparent)
+            })
+            .child(TextAreaElement { input: cx.entity() })
+    }
+}
+
+impl EventEmitter<TextAreaEvent> for TextArea {}
+
+impl Focusable for TextArea {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
@@ -1,14 +1,24 @@
+use crate::history::{History, Snapshot};
+use crate::mask::InputMask;
+use crate::text_utils;
 use crate::theme::ThemeAble;
+use crate::validator::Validator;
 use gpui::{
-    App, Bounds, ClipboardItem, Context, CursorStyle, ElementId, ElementInputHandler, Entity,
-    EntityInputHandler, FocusHandle, Focusable, GlobalElementId, LayoutId, MouseButton,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, Pixels, Point, ShapedLine,
-    SharedString, Style, Styled, TextRun, UTF16Selection, UnderlineStyle, Window, actions, div,
-    fill, hsla, point, prelude::*, px, relative, rgba, size,
+    App, Bounds, ClipboardItem, ContentMask, Context, CursorStyle, DispatchPhase, ElementId,
+    ElementInputHandler, Entity, EntityInputHandler, EventEmitter, FocusHandle, Focusable,
+    GlobalElementId, LayoutId, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    PaintQuad, Pixels, Point, ShapedLine, SharedString, Style, Styled, Subscription, Task, TextRun,
+    UTF16Selection, UnderlineStyle, Window, actions, div, fill, point, prelude::*, px, relative,
+    size,
 };
 use std::ops::Range;
+use std::time::Duration;
 use unicode_segmentation::*;
 
+const CURSOR_WIDTH: Pixels = px(2.);
+const BULLET: char = '•';
+const AUTOSCROLL_INTERVAL: Duration = Duration::from_millis(50);
+
 actions!(
     text_input,
     [
@@ -18,17 +28,45 @@ actions!(
         Right,
         SelectLeft,
         SelectRight,
+        WordLeft,
+        WordRight,
+        SelectWordLeft,
+        SelectWordRight,
+        DeleteWordLeft,
+        DeleteWordRight,
         SelectAll,
         Home,
         End,
+        SelectToHome,
+        SelectToEnd,
         ShowCharacterPalette,
         Paste,
         Cut,
         Copy,
+        Undo,
+        Redo,
+        Enter,
         Quit,
     ]
 );
 
+pub enum TextInputEvent {
+    Changed {
+        old: SharedString,
+        new: SharedString,
+    },
+    Submitted,
+    Focused,
+    Blurred,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SelectionMode {
+    Character,
+    Word,
+    Line,
+}
+
 pub struct TextInput {
     pub focus_handle: FocusHandle,
     pub content: SharedString,
@@ -40,12 +78,38 @@ pub struct TextInput {
     pub last_bounds: Option<Bounds<Pixels>>,
     pub is_selecting: bool,
     pub disabled: bool,
+    selection_mode: SelectionMode,
+    selection_anchor: Range<usize>,
+    scroll_x: Pixels,
+    drag_position: Point<Pixels>,
+    // Keeps extending the selection while a drag is held outside the field.
+    autoscroll: Option<Task<()>>,
+    history: History,
+    // How the field looked before the current IME composition, restored if
+    // the validator rejects it.
+    composition_start: Option<Snapshot>,
+    validator: Option<Box<dyn Validator>>,
+    mask: Option<InputMask>,
+    secure: bool,
+    revealed: bool,
+    reveal_toggle: bool,
+    _focus_subscriptions: Vec<Subscription>,
 }
 
 impl TextInput {
-    pub fn new(cx: &App) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        let focus_subscriptions = vec![
+            cx.on_focus(&focus_handle, window, |_, _, cx| {
+                cx.emit(TextInputEvent::Focused)
+            }),
+            cx.on_blur(&focus_handle, window, |_, _, cx| {
+                cx.emit(TextInputEvent::Blurred)
+            }),
+        ];
+
         Self {
-            focus_handle: cx.focus_handle(),
+            focus_handle,
             content: "".into(),
             placeholder: "".into(),
             selected_range: 0..0,
@@ -55,6 +119,19 @@ impl TextInput {
             last_bounds: None,
             is_selecting: false,
             disabled: false,
+            selection_mode: SelectionMode::Character,
+            selection_anchor: 0..0,
+            scroll_x: px(0.),
+            drag_position: Point::default(),
+            autoscroll: None,
+            history: History::default(),
+            composition_start: None,
+            validator: None,
+            mask: None,
+            secure: false,
+            revealed: false,
+            reveal_toggle: false,
+            _focus_subscriptions: focus_subscriptions,
         }
     }
 
@@ -67,6 +144,66 @@ impl TextInput {
         self
     }
 
+    pub fn validator(mut self, validator: impl Validator + 'static) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
+    pub fn mask(mut self, mask: InputMask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Renders a bullet per grapheme instead of the content and keeps it off
+    /// the clipboard.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Shows a button in secure fields that toggles between bullets and the
+    /// real content.
+    pub fn reveal_toggle(mut self, reveal_toggle: bool) -> Self {
+        self.reveal_toggle = reveal_toggle;
+        self
+    }
+
+    pub fn set_revealed(&mut self, revealed: bool, cx: &mut Context<Self>) {
+        self.revealed = revealed;
+        cx.notify()
+    }
+
+    /// The content without the mask's literal separators, or the content as-is
+    /// when no mask is set.
+    pub fn unmasked_value(&self) -> String {
+        match &self.mask {
+            Some(mask) => mask.unmask(&self.content),
+            None => self.content.to_string(),
+        }
+    }
+
+    pub fn validation_error(&self) -> Option<SharedString> {
+        self.validator
+            .as_ref()
+            .and_then(|validator| validator.validate(&self.content).err())
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validation_error().is_none()
+    }
+
+    fn is_pending(&self) -> bool {
+        self.validator
+            .as_ref()
+            .is_some_and(|validator| validator.is_pending(&self.content))
+    }
+
+    fn allows(&self, text: &str) -> bool {
+        self.validator
+            .as_ref()
+            .is_none_or(|validator| text.chars().all(|ch| validator.allows(ch)))
+    }
+
     fn left(&mut self, _: &Left, _: &mut Window, cx: &mut Context<Self>) {
         if self.selected_range.is_empty() {
             self.move_to(self.previous_boundary(self.cursor_offset()), cx);
@@ -91,6 +228,22 @@ impl TextInput {
         self.select_to(self.next_boundary(self.cursor_offset()), cx);
     }
 
+    fn word_left(&mut self, _: &WordLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.previous_word_boundary(self.cursor_offset()), cx);
+    }
+
+    fn word_right(&mut self, _: &WordRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_to(self.next_word_boundary(self.cursor_offset()), cx);
+    }
+
+    fn select_word_left(&mut self, _: &SelectWordLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.previous_word_boundary(self.cursor_offset()), cx);
+    }
+
+    fn select_word_right(&mut self, _: &SelectWordRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.next_word_boundary(self.cursor_offset()), cx);
+    }
+
     fn select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
         self.move_to(0, cx);
         self.select_to(self.content.len(), cx)
@@ -104,6 +257,14 @@ impl TextInput {
         self.move_to(self.content.len(), cx);
     }
 
+    fn select_to_home(&mut self, _: &SelectToHome, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(0, cx);
+    }
+
+    fn select_to_end(&mut self, _: &SelectToEnd, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.content.len(), cx);
+    }
+
     fn backspace(&mut self, _: &Backspace, window: &mut Window, cx: &mut Context<Self>) {
         if self.selected_range.is_empty() {
             self.select_to(self.previous_boundary(self.cursor_offset()), cx)
@@ -118,6 +279,30 @@ impl TextInput {
         self.replace_text_in_range(None, "", window, cx)
     }
 
+    fn delete_word_left(
+        &mut self,
+        _: &DeleteWordLeft,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.previous_word_boundary(self.cursor_offset()), cx)
+        }
+        self.replace_text_in_range(None, "", window, cx)
+    }
+
+    fn delete_word_right(
+        &mut self,
+        _: &DeleteWordRight,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.next_word_boundary(self.cursor_offset()), cx)
+        }
+        self.replace_text_in_range(None, "", window, cx)
+    }
+
     fn on_mouse_down(
         &mut self,
         event: &MouseDownEvent,
@@ -125,21 +310,88 @@ impl TextInput {
         cx: &mut Context<Self>,
     ) {
         self.is_selecting = true;
-
-        if event.modifiers.shift {
-            self.select_to(self.index_for_mouse_position(event.position), cx);
-        } else {
-            self.move_to(self.index_for_mouse_position(event.position), cx)
+        let offset = self.index_for_mouse_position(event.position);
+
+        match event.click_count {
+            2 => {
+                self.selection_mode = SelectionMode::Word;
+                self.selection_anchor = self.word_range(offset);
+                self.selected_range = self.selection_anchor.clone();
+                self.selection_reversed = false;
+                cx.notify()
+            }
+            count if count >= 3 => {
+                self.selection_mode = SelectionMode::Line;
+                self.selected_range = 0..self.content.len();
+                self.selection_reversed = false;
+                cx.notify()
+            }
+            _ => {
+                self.selection_mode = SelectionMode::Character;
+                if event.modifiers.shift {
+                    self.select_to(offset, cx);
+                } else {
+                    self.move_to(offset, cx)
+                }
+            }
         }
     }
 
-    fn on_mouse_up(&mut self, _: &MouseUpEvent, _window: &mut Window, _: &mut Context<Self>) {
+    fn on_mouse_up(&mut self, _: &MouseUpEvent, _window: &mut Window, cx: &mut Context<Self>) {
         self.is_selecting = false;
+        self.autoscroll = None;
+        cx.notify()
     }
 
     fn on_mouse_move(&mut self, event: &MouseMoveEvent, _: &mut Window, cx: &mut Context<Self>) {
-        if self.is_selecting {
-            self.select_to(self.index_for_mouse_position(event.position), cx);
+        if !self.is_selecting {
+            return;
+        }
+
+        self.drag_position = event.position;
+        self.select_to_position(event.position, cx);
+        if self.autoscroll.is_none() && self.drag_is_outside() {
+            self.autoscroll = Some(cx.spawn(async move |this, cx| {
+                loop {
+                    cx.background_executor().timer(AUTOSCROLL_INTERVAL).await;
+                    let updated = this.update(cx, |this, cx| {
+                        // Selecting the same point again reaches further each
+                        // time, since the field scrolled to the last cursor.
+                        if this.drag_is_outside() {
+                            this.select_to_position(this.drag_position, cx);
+                        }
+                    });
+                    if updated.is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
+    }
+
+    fn drag_is_outside(&self) -> bool {
+        self.last_bounds.is_some_and(|bounds| {
+            self.drag_position.x < bounds.left() || self.drag_position.x > bounds.right()
+        })
+    }
+
+    fn select_to_position(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
+        let offset = self.index_for_mouse_position(position);
+        match self.selection_mode {
+            SelectionMode::Character => self.select_to(offset, cx),
+            SelectionMode::Word => {
+                // Extend by whole words, keeping the double-clicked word selected.
+                let word = self.word_range(offset);
+                let anchor = &self.selection_anchor;
+                self.selection_reversed = word.start < anchor.start;
+                self.selected_range = if self.selection_reversed {
+                    word.start..anchor.end
+                } else {
+                    anchor.start..word.end.max(anchor.end)
+                };
+                cx.notify()
+            }
+            SelectionMode::Line => {}
         }
     }
 
@@ -159,14 +411,15 @@ impl TextInput {
     }
 
     fn copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
-        if !self.selected_range.is_empty() {
+        if !self.secure && !self.selected_range.is_empty() {
             cx.write_to_clipboard(ClipboardItem::new_string(
                 self.content[self.selected_range.clone()].to_string(),
             ));
         }
     }
+
     fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
-        if !self.selected_range.is_empty() {
+        if !self.secure && !self.selected_range.is_empty() {
             cx.write_to_clipboard(ClipboardItem::new_string(
                 self.content[self.selected_range.clone()].to_string(),
             ));
@@ -174,8 +427,59 @@ impl TextInput {
         }
     }
 
+    fn undo(&mut self, _: &Undo, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(snapshot) = self.history.undo(self.snapshot()) {
+            self.restore(snapshot, cx);
+        }
+    }
+
+    fn redo(&mut self, _: &Redo, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(snapshot) = self.history.redo(self.snapshot()) {
+            self.restore(snapshot, cx);
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            content: self.content.clone(),
+            selected_range: self.selected_range.clone(),
+            selection_reversed: self.selection_reversed,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot, cx: &mut Context<Self>) {
+        let old = std::mem::replace(&mut self.content, snapshot.content);
+        self.selected_range = snapshot.selected_range;
+        self.selection_reversed = snapshot.selection_reversed;
+        self.marked_range = None;
+        self.composition_start = None;
+        self.emit_changed(old, cx);
+        cx.notify()
+    }
+
+    fn cancel_composition(&mut self, cx: &mut Context<Self>) {
+        self.marked_range = None;
+        if let Some(snapshot) = self.composition_start.take() {
+            self.restore(snapshot, cx);
+        }
+    }
+
+    fn emit_changed(&self, old: SharedString, cx: &mut Context<Self>) {
+        if old != self.content {
+            cx.emit(TextInputEvent::Changed {
+                old,
+                new: self.content.clone(),
+            });
+        }
+    }
+
+    fn enter(&mut self, _: &Enter, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(TextInputEvent::Submitted);
+    }
+
     fn move_to(&mut self, offset: usize, cx: &mut Context<Self>) {
         self.selected_range = offset..offset;
+        self.history.break_group();
         cx.notify()
     }
 
@@ -202,7 +506,43 @@ impl TextInput {
         if position.y > bounds.bottom() {
             return self.content.len();
         }
-        line.closest_index_for_x(position.x - bounds.left())
+        self.content_offset(line.closest_index_for_x(position.x - bounds.left() + self.scroll_x))
+    }
+
+    fn obscured(&self) -> bool {
+        self.secure && !self.revealed
+    }
+
+    fn display_content(&self) -> SharedString {
+        if self.obscured() {
+            BULLET
+                .to_string()
+                .repeat(self.content.graphemes(true).count())
+                .into()
+        } else {
+            self.content.clone()
+        }
+    }
+
+    /// Maps an offset in `content` to the matching offset in the laid out line,
+    /// which differ when the content is obscured by bullets.
+    fn display_offset(&self, offset: usize) -> usize {
+        if !self.obscured() {
+            return offset;
+        }
+        self.content[..offset].graphemes(true).count() * BULLET.len_utf8()
+    }
+
+    /// The inverse of [`Self::display_offset`]. Offsets in the mask's ghost
+    /// text after the content map to its end.
+    fn content_offset(&self, display_offset: usize) -> usize {
+        if !self.obscured() {
+            return display_offset.min(self.content.len());
+        }
+        self.content
+            .grapheme_indices(true)
+            .nth(display_offset / BULLET.len_utf8())
+            .map_or(self.content.len(), |(ix, _)| ix)
     }
 
     fn select_to(&mut self, offset: usize, cx: &mut Context<Self>) {
@@ -218,72 +558,88 @@ impl TextInput {
         cx.notify()
     }
 
-    fn offset_from_utf16(&self, offset: usize) -> usize {
-        let mut utf8_offset = 0;
-        let mut utf16_count = 0;
-
-        for ch in self.content.chars() {
-            if utf16_count >= offset {
-                break;
-            }
-            utf16_count += ch.len_utf16();
-            utf8_offset += ch.len_utf8();
-        }
-
-        utf8_offset
+    fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
+        text_utils::range_to_utf16(&self.content, range)
     }
 
-    fn offset_to_utf16(&self, offset: usize) -> usize {
-        let mut utf16_offset = 0;
-        let mut utf8_count = 0;
+    fn range_from_utf16(&self, range_utf16: &Range<usize>) -> Range<usize> {
+        text_utils::range_from_utf16(&self.content, range_utf16)
+    }
 
-        for ch in self.content.chars() {
-            if utf8_count >= offset {
-                break;
+    fn previous_boundary(&self, mut offset: usize) -> usize {
+        loop {
+            offset = text_utils::previous_boundary(&self.content, offset);
+            if offset == 0 || !self.is_mask_literal(offset) {
+                return offset;
             }
-            utf8_count += ch.len_utf8();
-            utf16_offset += ch.len_utf16();
         }
-
-        utf16_offset
     }
 
-    fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
-        self.offset_to_utf16(range.start)..self.offset_to_utf16(range.end)
+    fn next_boundary(&self, mut offset: usize) -> usize {
+        loop {
+            offset = text_utils::next_boundary(&self.content, offset);
+            if offset == self.content.len() || !self.is_mask_literal(offset) {
+                return offset;
+            }
+        }
     }
 
-    fn range_from_utf16(&self, range_utf16: &Range<usize>) -> Range<usize> {
-        self.offset_from_utf16(range_utf16.start)..self.offset_from_utf16(range_utf16.end)
+    /// Whether the character at `offset` is a separator inserted by the mask,
+    /// which cursor movement steps over.
+    fn is_mask_literal(&self, offset: usize) -> bool {
+        self.mask
+            .as_ref()
+            .is_some_and(|mask| mask.is_literal(self.content[..offset].chars().count()))
     }
 
-    fn previous_boundary(&self, offset: usize) -> usize {
-        self.content
-            .grapheme_indices(true)
-            .rev()
-            .find_map(|(idx, _)| (idx < offset).then_some(idx))
-            .unwrap_or(0)
+    // Obscured content is treated as a single word so its structure isn't
+    // revealed by word movement.
+    fn previous_word_boundary(&self, offset: usize) -> usize {
+        if self.obscured() {
+            return 0;
+        }
+        text_utils::previous_word_boundary(&self.content, offset)
     }
 
-    fn next_boundary(&self, offset: usize) -> usize {
-        self.content
-            .grapheme_indices(true)
-            .find_map(|(idx, _)| (idx > offset).then_some(idx))
-            .unwrap_or(self.content.len())
+    fn next_word_boundary(&self, offset: usize) -> usize {
+        if self.obscured() {
+            return self.content.len();
+        }
+        text_utils::next_word_boundary(&self.content, offset)
     }
 
-    pub fn set_content(&mut self, new_content: String) {
-        self.reset();
-        self.content = new_content.into();
+    fn word_range(&self, offset: usize) -> Range<usize> {
+        if self.obscured() {
+            return 0..self.content.len();
+        }
+        text_utils::word_range(&self.content, offset)
     }
 
-    pub fn reset(&mut self) {
-        self.content = "".into();
+    /// Replaces the content, clearing the selection and undo history, and
+    /// emits [`TextInputEvent::Changed`] if it differs.
+    pub fn set_content(&mut self, new_content: String, cx: &mut Context<Self>) {
+        let old = std::mem::replace(&mut self.content, new_content.into());
         self.selected_range = 0..0;
         self.selection_reversed = false;
         self.marked_range = None;
         self.last_layout = None;
         self.last_bounds = None;
         self.is_selecting = false;
+        self.autoscroll = None;
+        self.scroll_x = px(0.);
+        self.history.clear();
+        self.composition_start = None;
+        self.emit_changed(old, cx);
+        cx.notify()
+    }
+
+    /// Puts the cursor after the last character.
+    pub fn move_to_end(&mut self, cx: &mut Context<Self>) {
+        self.move_to(self.content.len(), cx);
+    }
+
+    pub fn reset(&mut self, cx: &mut Context<Self>) {
+        self.set_content(String::new(), cx);
     }
 }
 
@@ -324,6 +680,7 @@ impl EntityInputHandler for TextInput {
 
     fn unmark_text(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
         self.marked_range = None;
+        self.composition_start = None;
     }
 
     fn replace_text_in_range(
@@ -339,11 +696,29 @@ impl EntityInputHandler for TextInput {
             .or(self.marked_range.clone())
             .unwrap_or(self.selected_range.clone());
 
-        self.content =
-            (self.content[0..range.start].to_owned() + new_text + &self.content[range.end..])
-                .into();
-        self.selected_range = range.start + new_text.len()..range.start + new_text.len();
+        if !self.allows(new_text) {
+            self.cancel_composition(cx);
+            return;
+        }
+
+        let mut new_content =
+            self.content[0..range.start].to_owned() + new_text + &self.content[range.end..];
+        let mut cursor = range.start + new_text.len();
+        if let Some(mask) = &self.mask {
+            cursor = mask.apply(&new_content[..cursor]).len();
+            new_content = mask.apply(&new_content);
+        }
+
+        // A composition was recorded as a whole when it started.
+        if self.marked_range.is_none() && new_content != self.content.as_ref() {
+            self.history.record(self.snapshot(), &range, new_text);
+        }
+
+        let old = std::mem::replace(&mut self.content, new_content.into());
+        self.selected_range = cursor..cursor;
         self.marked_range.take();
+        self.composition_start = None;
+        self.emit_changed(old, cx);
         cx.notify();
     }
 
@@ -352,7 +727,7 @@ impl EntityInputHandler for TextInput {
         range_utf16: Option<Range<usize>>,
         new_text: &str,
         new_selected_range_utf16: Option<Range<usize>>,
-        _window: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         let range = range_utf16
@@ -361,6 +736,25 @@ impl EntityInputHandler for TextInput {
             .or(self.marked_range.clone())
             .unwrap_or(self.selected_range.clone());
 
+        // Masked fields only hold fixed formats, so compositions are committed
+        // straight away rather than shown as marked text.
+        if self.mask.is_some() {
+            self.replace_text_in_range(range_utf16, new_text, window, cx);
+            return;
+        }
+
+        if !self.allows(new_text) {
+            self.cancel_composition(cx);
+            return;
+        }
+
+        if self.marked_range.is_none() {
+            self.composition_start = Some(self.snapshot());
+            self.history.record(self.snapshot(), &range, new_text);
+            self.history.break_group();
+        }
+
+        let old = self.content.clone();
         self.content =
             (self.content[0..range.start].to_owned() + new_text + &self.content[range.end..])
                 .into();
@@ -368,6 +762,7 @@ impl EntityInputHandler for TextInput {
             self.marked_range = Some(range.start..range.start + new_text.len());
         } else {
             self.marked_range = None;
+            self.composition_start = None;
         }
         self.selected_range = new_selected_range_utf16
             .as_ref()
@@ -375,6 +770,7 @@ impl EntityInputHandler for TextInput {
             .map(|new_range| new_range.start + range.start..new_range.end + range.end)
             .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
 
+        self.emit_changed(old, cx);
         cx.notify();
     }
 
@@ -387,15 +783,12 @@ impl EntityInputHandler for TextInput {
     ) -> Option<Bounds<Pixels>> {
         let last_layout = self.last_layout.as_ref()?;
         let range = self.range_from_utf16(&range_utf16);
+        let start = last_layout.x_for_index(self.display_offset(range.start));
+        let end = last_layout.x_for_index(self.display_offset(range.end));
+        let left = bounds.left() - self.scroll_x;
         Some(Bounds::from_corners(
-            point(
-                bounds.left() + last_layout.x_for_index(range.start),
-                bounds.top(),
-            ),
-            point(
-                bounds.left() + last_layout.x_for_index(range.end),
-                bounds.bottom(),
-            ),
+            point(left + start, bounds.top()),
+            point(left + end, bounds.bottom()),
         ))
     }
 
@@ -406,11 +799,14 @@ impl EntityInputHandler for TextInput {
         _cx: &mut Context<Self>,
     ) -> Option<usize> {
         let line_point = self.last_bounds?.localize(&point)?;
-        let last_layout = self.last_layout.as_ref()?;
-
-        assert_eq!(last_layout.text, self.content);
-        let utf8_index = last_layout.index_for_x(point.x - line_point.x)?;
-        Some(self.offset_to_utf16(utf8_index))
+        // The platform may ask before a content change has been laid out.
+        let last_layout = self
+            .last_layout
+            .as_ref()
+            .filter(|layout| layout.text.starts_with(self.display_content().as_ref()))?;
+        let display_index = last_layout.index_for_x(line_point.x + self.scroll_x)?;
+        let utf8_index = self.content_offset(display_index);
+        Some(text_utils::offset_to_utf16(&self.content, utf8_index))
     }
 }
 
@@ -420,6 +816,7 @@ struct TextElement {
 
 struct PrepaintState {
     line: Option<ShapedLine>,
+    scroll_x: Pixels,
     cursor: Option<PaintQuad>,
     selection: Option<PaintQuad>,
 }
@@ -467,13 +864,26 @@ impl Element for TextElement {
         cx: &mut App,
     ) -> Self::PrepaintState {
         let input = self.input.read(cx);
-        let content = input.content.clone();
-        let selected_range = input.selected_range.clone();
-        let cursor = input.cursor_offset();
+        let content = input.display_content();
+        let selected_range = input.display_offset(input.selected_range.start)
+            ..input.display_offset(input.selected_range.end);
+        let marked_range = input
+            .marked_range
+            .as_ref()
+            .map(|range| input.display_offset(range.start)..input.display_offset(range.end));
+        let cursor = input.display_offset(input.cursor_offset());
         let style = window.text_style();
 
-        let (display_text, text_color) = if content.is_empty() {
-            (input.placeholder.clone(), hsla(0., 0., 0., 0.2))
+        let theme = cx.theme();
+        let placeholder_color = theme.placeholder;
+        let ghost = input
+            .mask
+            .as_ref()
+            .map(|mask| mask.ghost_after(&content))
+            .unwrap_or_default();
+
+        let (display_text, text_color) = if content.is_empty() && ghost.is_empty() {
+            (input.placeholder.clone(), placeholder_color)
         } else {
             (content, style.color)
         };
@@ -486,7 +896,7 @@ impl Element for TextElement {
             underline: None,
             strikethrough: None,
         };
-        let runs = if let Some(marked_range) = input.marked_range.as_ref() {
+        let runs = if let Some(marked_range) = marked_range {
             vec![
                 TextRun {
                     len: marked_range.start,
@@ -513,43 +923,64 @@ impl Element for TextElement {
             vec![run]
         };
 
+        let (display_text, runs) = if ghost.is_empty() {
+            (display_text, runs)
+        } else {
+            let ghost_run = TextRun {
+                len: ghost.len(),
+                color: placeholder_color,
+                ..runs[0].clone()
+            };
+            let runs = runs
+                .into_iter()
+                .chain([ghost_run])
+                .filter(|run| run.len > 0)
+                .collect::<Vec<_>>();
+            (format!("{display_text}{ghost}").into(), runs)
+        };
+
         let font_size = style.font_size.to_pixels(window.rem_size());
         let line = window
             .text_system()
             .shape_line(display_text, font_size, &runs, None);
 
+        // Scroll just far enough to keep the cursor, including its width, in view.
         let cursor_pos = line.x_for_index(cursor);
+        let max_scroll = (line.width + CURSOR_WIDTH - bounds.size.width).max(px(0.));
+        let mut scroll_x = input.scroll_x.min(max_scroll);
+        if cursor_pos < scroll_x {
+            scroll_x = cursor_pos;
+        } else if cursor_pos + CURSOR_WIDTH > scroll_x + bounds.size.width {
+            scroll_x = cursor_pos + CURSOR_WIDTH - bounds.size.width;
+        }
+        let left = bounds.left() - scroll_x;
+
         let (selection, cursor) = if selected_range.is_empty() {
             (
                 None,
                 Some(fill(
                     Bounds::new(
-                        point(bounds.left() + cursor_pos, bounds.top()),
-                        size(px(2.), bounds.bottom() - bounds.top()),
+                        point(left + cursor_pos, bounds.top()),
+                        size(CURSOR_WIDTH, bounds.bottom() - bounds.top()),
                     ),
-                    gpui::blue(),
+                    theme.caret,
                 )),
             )
         } else {
             (
                 Some(fill(
                     Bounds::from_corners(
-                        point(
-                            bounds.left() + line.x_for_index(selected_range.start),
-                            bounds.top(),
-                        ),
-                        point(
-                            bounds.left() + line.x_for_index(selected_range.end),
-                            bounds.bottom(),
-                        ),
+                        point(left + line.x_for_index(selected_range.start), bounds.top()),
+                        point(left + line.x_for_index(selected_range.end), bounds.bottom()),
                     ),
-                    rgba(0x3311ff30),
+                    theme.selection,
                 )),
                 None,
             )
         };
         PrepaintState {
             line: Some(line),
+            scroll_x,
             cursor,
             selection,
         }
@@ -571,40 +1002,76 @@ impl Element for TextElement {
             ElementInputHandler::new(bounds, self.input.clone()),
             cx,
         );
-        if let Some(selection) = prepaint.selection.take() {
-            window.paint_quad(selection)
+        // A selection drag is followed outside the field too, so it can
+        // scroll the content.
+        if self.input.read(cx).is_selecting {
+            let input = self.input.clone();
+            window.on_mouse_event(move |event: &MouseMoveEvent, phase, window, cx| {
+                if phase == DispatchPhase::Bubble {
+                    input.update(cx, |input, cx| input.on_mouse_move(event, window, cx));
+                }
+            });
+            let input = self.input.clone();
+            window.on_mouse_event(move |event: &MouseUpEvent, phase, window, cx| {
+                if phase == DispatchPhase::Bubble && event.button == MouseButton::Left {
+                    input.update(cx, |input, cx| input.on_mouse_up(event, window, cx));
+                }
+            });
         }
+
         let line = prepaint.line.take().unwrap();
-        line.paint(bounds.origin, window.line_height(), window, cx)
+        let scroll_x = prepaint.scroll_x;
+        window.with_content_mask(Some(ContentMask { bounds }), |window| {
+            if let Some(selection) = prepaint.selection.take() {
+                window.paint_quad(selection)
+            }
+            line.paint(
+                point(bounds.left() - scroll_x, bounds.top()),
+                window.line_height(),
+                window,
+                cx,
+            )
             .unwrap();
 
-        if focus_handle.is_focused(window)
-            && let Some(cursor) = prepaint.cursor.take()
-        {
-            window.paint_quad(cursor);
-        }
+            if focus_handle.is_focused(window)
+                && let Some(cursor) = prepaint.cursor.take()
+            {
+                window.paint_quad(cursor);
+            }
+        });
 
         self.input.update(cx, |input, _cx| {
             input.last_layout = Some(line);
             input.last_bounds = Some(bounds);
+            input.scroll_x = scroll_x;
         });
     }
 }
 
 impl Render for TextInput {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focused = self.focus_handle.is_focused(window);
         let theme = cx.theme();
+        // Don't flag text the user is still in the middle of typing.
+        let hold_back = self.disabled || (focused && self.is_pending());
+        let error = self.validation_error().filter(|_| !hold_back);
 
-        div()
+        let field = div()
             .flex()
             .justify_center()
             .items_center()
-            .px_2()
+            .px(theme.spacing.md)
             .w_full()
             .h(px(22. + 4. * 2.))
             .border_1()
-            .border_color(theme.border)
-            .rounded_md()
+            .border_color(if error.is_some() {
+                theme.danger
+            } else if focused {
+                theme.focus_ring
+            } else {
+                theme.border
+            })
+            .rounded(theme.radius.md)
             .overflow_hidden()
             .key_context("TextInput")
             .track_focus(&self.focus_handle(cx))
@@ -619,20 +1086,28 @@ impl Render for TextInput {
                     .on_action(cx.listener(Self::right))
                     .on_action(cx.listener(Self::select_left))
                     .on_action(cx.listener(Self::select_right))
+                    .on_action(cx.listener(Self::word_left))
+                    .on_action(cx.listener(Self::word_right))
+                    .on_action(cx.listener(Self::select_word_left))
+                    .on_action(cx.listener(Self::select_word_right))
+                    .on_action(cx.listener(Self::delete_word_left))
+                    .on_action(cx.listener(Self::delete_word_right))
                     .on_action(cx.listener(Self::select_all))
                     .on_action(cx.listener(Self::home))
                     .on_action(cx.listener(Self::end))
+                    .on_action(cx.listener(Self::select_to_home))
+                    .on_action(cx.listener(Self::select_to_end))
                     .on_action(cx.listener(Self::show_character_palette))
                     .on_action(cx.listener(Self::paste))
                     .on_action(cx.listener(Self::cut))
                     .on_action(cx.listener(Self::copy))
+                    .on_action(cx.listener(Self::undo))
+                    .on_action(cx.listener(Self::redo))
+                    .on_action(cx.listener(Self::enter))
                     .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
-                    .on_mouse_move(cx.listener(Self::on_mouse_move))
-                    .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
-                    .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
                     .bg(theme.transparent)
-                    .line_height(px(18.))
-                    .text_size(px(16.))
+                    .line_height(theme.text.line_height)
+                    .text_size(theme.text.md)
                     .child(
                         div()
                             .w_full()
@@ -640,12 +1115,46 @@ impl Render for TextInput {
                             .flex()
                             .items_center()
                             .justify_start()
-                            .child(TextElement { input: cx.entity() }),
+                            .child(TextElement { input: cx.entity() })
+                            .when(self.secure && self.reveal_toggle, |this| {
+                                this.child(
+                                    div()
+                                        .flex_none()
+                                        .pl(theme.spacing.sm)
+                                        .text_size(theme.text.xs)
+                                        .cursor(CursorStyle::PointingHand)
+                                        .child(if self.revealed { "Hide" } else { "Show" })
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener(|this, _, _, cx| {
+                                                cx.stop_propagation();
+                                                this.set_revealed(!this.revealed, cx);
+                                            }),
+                                        ),
+                                )
+                            }),
                     )
+            });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(theme.spacing.xs)
+            .w_full()
+            .child(field)
+            .when_some(error, |this, error| {
+                this.child(
+                    div()
+                        .text_size(theme.text.xs)
+                        .text_color(theme.danger)
+                        .child(error),
+                )
             })
     }
 }
 
+impl EventEmitter<TextInputEvent> for TextInput {}
+
 impl Focusable for TextInput {
     fn focus_handle(&self, _: &App) -> FocusHandle {
         self.focus_handle.clone()
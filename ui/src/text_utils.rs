@@ -0,0 +1,135 @@
+use std::ops::Range;
+use unicode_segmentation::*;
+
+pub(crate) fn offset_from_utf16(text: &str, offset: usize) -> usize {
+    let mut utf8_offset = 0;
+    let mut utf16_count = 0;
+
+    for ch in text.chars() {
+        if utf16_count >= offset {
+            break;
+        }
+        utf16_count += ch.len_utf16();
+        utf8_offset += ch.len_utf8();
+    }
+
+    utf8_offset
+}
+
+pub(crate) fn offset_to_utf16(text: &str, offset: usize) -> usize {
+    let mut utf16_offset = 0;
+    let mut utf8_count = 0;
+
+    for ch in text.chars() {
+        if utf8_count >= offset {
+            break;
+        }
+        utf8_count += ch.len_utf8();
+        utf16_offset += ch.len_utf16();
+    }
+
+    utf16_offset
+}
+
+pub(crate) fn range_to_utf16(text: &str, range: &Range<usize>) -> Range<usize> {
+    offset_to_utf16(text, range.start)..offset_to_utf16(text, range.end)
+}
+
+pub(crate) fn range_from_utf16(text: &str, range_utf16: &Range<usize>) -> Range<usize> {
+    offset_from_utf16(text, range_utf16.start)..offset_from_utf16(text, range_utf16.end)
+}
+
+pub(crate) fn previous_boundary(text: &str, offset: usize) -> usize {
+    text.grapheme_indices(true)
+        .rev()
+        .find_map(|(idx, _)| (idx < offset).then_some(idx))
+        .unwrap_or(0)
+}
+
+pub(crate) fn next_boundary(text: &str, offset: usize) -> usize {
+    text.grapheme_indices(true)
+        .find_map(|(idx, _)| (idx > offset).then_some(idx))
+        .unwrap_or(text.len())
+}
+
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(char::is_alphanumeric)
+}
+
+pub(crate) fn previous_word_boundary(text: &str, offset: usize) -> usize {
+    text.split_word_bound_indices()
+        .rev()
+        .find(|(idx, segment)| *idx < offset && is_word(segment))
+        .map_or(0, |(idx, _)| idx)
+}
+
+pub(crate) fn next_word_boundary(text: &str, offset: usize) -> usize {
+    text.split_word_bound_indices()
+        .find(|(idx, segment)| idx + segment.len() > offset && is_word(segment))
+        .map_or(text.len(), |(idx, segment)| idx + segment.len())
+}
+
+pub(crate) fn word_range(text: &str, offset: usize) -> Range<usize> {
+    text.split_word_bound_indices()
+        .find(|(idx, segment)| offset < idx + segment.len())
+        .or_else(|| text.split_word_bound_indices().next_back())
+        .map_or(0..0, |(idx, segment)| idx..idx + segment.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "hello, wörld  foo";
+
+    #[test]
+    fn utf16_offsets_round_trip() {
+        let text = "a😀b";
+        assert_eq!(offset_to_utf16(text, 5), 3);
+        assert_eq!(offset_from_utf16(text, 3), 5);
+        assert_eq!(range_to_utf16(text, &(0..6)), 0..4);
+        assert_eq!(range_from_utf16(text, &(1..3)), 1..5);
+        assert_eq!(offset_from_utf16(text, 10), text.len());
+    }
+
+    #[test]
+    fn grapheme_boundaries_skip_combining_marks() {
+        let text = "e\u{301}x";
+        assert_eq!(next_boundary(text, 0), 3);
+        assert_eq!(next_boundary(text, 3), text.len());
+        assert_eq!(previous_boundary(text, 3), 0);
+        assert_eq!(previous_boundary(text, 0), 0);
+    }
+
+    #[test]
+    fn previous_word_boundary_stops_at_word_starts() {
+        let world = TEXT.find("wörld").unwrap();
+        let foo = TEXT.find("foo").unwrap();
+        assert_eq!(previous_word_boundary(TEXT, TEXT.len()), foo);
+        assert_eq!(previous_word_boundary(TEXT, foo), world);
+        assert_eq!(previous_word_boundary(TEXT, world), 0);
+        assert_eq!(previous_word_boundary(TEXT, 0), 0);
+    }
+
+    #[test]
+    fn next_word_boundary_stops_at_word_ends() {
+        let world_end = TEXT.find("wörld").unwrap() + "wörld".len();
+        assert_eq!(next_word_boundary(TEXT, 0), 5);
+        assert_eq!(next_word_boundary(TEXT, 5), world_end);
+        assert_eq!(next_word_boundary(TEXT, world_end), TEXT.len());
+        assert_eq!(next_word_boundary(TEXT, TEXT.len()), TEXT.len());
+    }
+
+    #[test]
+    fn word_range_covers_the_segment_at_offset() {
+        let world = TEXT.find("wörld").unwrap();
+        assert_eq!(word_range(TEXT, 1), 0..5);
+        assert_eq!(word_range(TEXT, world + 1), world..world + "wörld".len());
+        assert_eq!(word_range(TEXT, 5), 5..6);
+        assert_eq!(
+            word_range(TEXT, TEXT.len()),
+            TEXT.find("foo").unwrap()..TEXT.len()
+        );
+        assert_eq!(word_range("", 0), 0..0);
+    }
+}
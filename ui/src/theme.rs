@@ -1,4 +1,4 @@
-use gpui::{App, Global, Hsla, hsla, rgb, rgba};
+use gpui::{App, Global, Hsla, Pixels, Window, WindowAppearance, hsla, px, rgb, rgba};
 
 pub trait ThemeAble {
     fn theme(&self) -> &Theme;
@@ -13,17 +13,114 @@ impl ThemeAble for App {
 pub struct Theme {
     pub border: Hsla,
     pub button_surface: Hsla,
+    pub caret: Hsla,
+    pub danger: Hsla,
+    pub focus_ring: Hsla,
     pub foreground: Hsla,
     pub ground: Hsla,
     pub highlight: Hsla,
+    /// Secondary content such as icons.
+    pub muted_foreground: Hsla,
+    /// Dims the window behind modal content.
+    pub overlay: Hsla,
+    pub placeholder: Hsla,
     pub primary: Hsla,
+    /// Text and icons drawn on top of `primary`.
+    pub primary_foreground: Hsla,
     pub primary_hover: Hsla,
+    pub selection: Hsla,
+    pub success: Hsla,
     pub surface: Hsla,
     pub transparent: Hsla,
+    pub warning: Hsla,
+    pub radius: Radius,
+    pub spacing: Spacing,
+    pub text: Typography,
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct Radius {
+    pub sm: Pixels,
+    pub md: Pixels,
+    pub lg: Pixels,
+}
+
+impl Default for Radius {
+    fn default() -> Self {
+        Self {
+            sm: px(4.),
+            md: px(6.),
+            lg: px(8.),
+        }
+    }
+}
+
+/// Gaps and paddings, from the tightest to the loosest.
+#[derive(Clone, Copy, Debug)]
+pub struct Spacing {
+    pub xs: Pixels,
+    pub sm: Pixels,
+    pub md: Pixels,
+    pub lg: Pixels,
+    pub xl: Pixels,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            xs: px(2.),
+            sm: px(4.),
+            md: px(8.),
+            lg: px(12.),
+            xl: px(16.),
+        }
+    }
+}
+
+/// Font sizes. `md` is used for editable text, which is laid out with
+/// `line_height`.
+#[derive(Clone, Copy, Debug)]
+pub struct Typography {
+    pub xs: Pixels,
+    pub sm: Pixels,
+    pub md: Pixels,
+    pub line_height: Pixels,
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            xs: px(12.),
+            sm: px(14.),
+            md: px(16.),
+            line_height: px(18.),
+        }
+    }
+}
+
+/// Which palette [`Theme`] uses. `System` picks light or dark to match the
+/// appearance the platform reports for the app's windows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+// `None` while a theme passed to `Theme::apply` is in use.
+struct ActiveThemeMode(Option<ThemeMode>);
+
+impl Global for ActiveThemeMode {}
+
 impl Default for Theme {
     fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl Theme {
+    pub fn light() -> Self {
         Self {
             primary: rgba(0x0078D4FF).into(),
             primary_hover: rgba(0x0078D4DD).into(),
@@ -32,8 +129,59 @@ impl Default for Theme {
             ground: rgb(0xF3F4F6).into(),
             surface: rgb(0xFFFFFF).into(),
             button_surface: rgb(0xFFFFFF).into(),
+            danger: rgb(0xD13438).into(),
+            success: rgb(0x107C10).into(),
+            warning: rgb(0xC19C00).into(),
             transparent: hsla(0., 0., 0., 0.),
             highlight: rgb(0xFAFAFA).into(),
+            muted_foreground: rgb(0x555555).into(),
+            overlay: hsla(0., 0., 0., 0.3),
+            primary_foreground: rgb(0xFFFFFF).into(),
+            placeholder: hsla(0., 0., 0., 0.2),
+            caret: rgb(0x0078D4).into(),
+            selection: rgba(0x0078D433).into(),
+            focus_ring: rgb(0x0078D4).into(),
+            radius: Radius::default(),
+            spacing: Spacing::default(),
+            text: Typography::default(),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            primary: rgba(0x2899F5FF).into(),
+            primary_hover: rgba(0x2899F5DD).into(),
+            foreground: rgb(0xE4E4E7).into(),
+            border: rgba(0x52525BBB).into(),
+            ground: rgb(0x18181B).into(),
+            surface: rgb(0x27272A).into(),
+            button_surface: rgb(0x2F2F33).into(),
+            danger: rgb(0xF1707B).into(),
+            success: rgb(0x6CCB5F).into(),
+            warning: rgb(0xFCE100).into(),
+            transparent: hsla(0., 0., 0., 0.),
+            highlight: rgb(0x3A3A40).into(),
+            muted_foreground: rgb(0xA1A1AA).into(),
+            overlay: hsla(0., 0., 0., 0.5),
+            primary_foreground: rgb(0xFFFFFF).into(),
+            placeholder: hsla(0., 0., 1., 0.3),
+            caret: rgb(0x2899F5).into(),
+            selection: rgba(0x2899F550).into(),
+            focus_ring: rgb(0x2899F5).into(),
+            radius: Radius::default(),
+            spacing: Spacing::default(),
+            text: Typography::default(),
+        }
+    }
+
+    fn for_mode(mode: ThemeMode, appearance: WindowAppearance) -> Self {
+        match (mode, appearance) {
+            (ThemeMode::Light, _) => Self::light(),
+            (ThemeMode::Dark, _) => Self::dark(),
+            (ThemeMode::System, WindowAppearance::Dark | WindowAppearance::VibrantDark) => {
+                Self::dark()
+            }
+            (ThemeMode::System, _) => Self::light(),
         }
     }
 }
@@ -42,6 +190,41 @@ impl Global for Theme {}
 
 impl Theme {
     pub fn init(cx: &mut App) {
-        cx.set_global(Theme::default());
+        Self::set(cx, ThemeMode::System);
+    }
+
+    /// Switches to `mode` and redraws every open window.
+    pub fn set(cx: &mut App, mode: ThemeMode) {
+        cx.set_global(ActiveThemeMode(Some(mode)));
+        cx.set_global(Self::for_mode(mode, cx.window_appearance()));
+        cx.refresh_windows();
+    }
+
+    /// Replaces the palette with `theme`, e.g. one read by [`Theme::load`], and
+    /// redraws every open window. It stays until the next `set` or `apply`.
+    pub fn apply(cx: &mut App, theme: Theme) {
+        cx.set_global(ActiveThemeMode(None));
+        cx.set_global(theme);
+        cx.refresh_windows();
+    }
+
+    /// The mode last passed to [`Theme::set`], or `None` if a custom theme
+    /// has been applied since.
+    pub fn mode(cx: &App) -> Option<ThemeMode> {
+        cx.try_global::<ActiveThemeMode>()
+            .map_or(Some(ThemeMode::default()), |mode| mode.0)
+    }
+
+    /// Re-resolves the `System` palette whenever `window` changes appearance.
+    /// Call it once for each window the app opens.
+    pub fn observe_window(window: &Window) {
+        window
+            .observe_window_appearance(|window, cx| {
+                if Self::mode(cx) == Some(ThemeMode::System) {
+                    cx.set_global(Self::for_mode(ThemeMode::System, window.appearance()));
+                    cx.refresh_windows();
+                }
+            })
+            .detach();
     }
 }
@@ -0,0 +1,371 @@
+use crate::theme::Theme;
+use gpui::{App, Hsla, Pixels, Task, px, rgba};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fmt, fs, io};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A problem found while loading a theme file.
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    UnsupportedFormat(PathBuf),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io(error) => write!(f, "failed to read theme: {error}"),
+            ThemeError::Toml(error) => write!(f, "invalid theme: {error}"),
+            ThemeError::Json(error) => write!(f, "invalid theme: {error}"),
+            ThemeError::UnsupportedFormat(path) => write!(
+                f,
+                "unsupported theme file {}: expected a .toml or .json extension",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+/// A color written as `#RRGGBB` or `#RRGGBBAA`.
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct HexColor(Hsla);
+
+impl TryFrom<String> for HexColor {
+    type Error = String;
+
+    fn try_from(hex: String) -> Result<Self, Self::Error> {
+        let digits = hex.strip_prefix('#').unwrap_or(&hex);
+        // `from_str_radix` would also accept a leading sign.
+        let value = u32::from_str_radix(digits, 16)
+            .ok()
+            .filter(|_| digits.bytes().all(|byte| byte.is_ascii_hexdigit()));
+        match (digits.len(), value) {
+            (6, Some(value)) => Ok(Self(rgba(value << 8 | 0xFF).into())),
+            (8, Some(value)) => Ok(Self(rgba(value).into())),
+            _ => Err(format!(
+                "invalid color \"{hex}\", expected #RRGGBB or #RRGGBBAA"
+            )),
+        }
+    }
+}
+
+/// A length in pixels, which can't be negative.
+#[derive(Deserialize)]
+#[serde(try_from = "f32")]
+struct Length(Pixels);
+
+impl TryFrom<f32> for Length {
+    type Error = String;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        if value.is_finite() && value >= 0. {
+            Ok(Self(px(value)))
+        } else {
+            Err(format!(
+                "invalid length {value}, expected pixels of at least 0"
+            ))
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct RadiusFile {
+    sm: Option<Length>,
+    md: Option<Length>,
+    lg: Option<Length>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct SpacingFile {
+    xs: Option<Length>,
+    sm: Option<Length>,
+    md: Option<Length>,
+    lg: Option<Length>,
+    xl: Option<Length>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct TypographyFile {
+    xs: Option<Length>,
+    sm: Option<Length>,
+    md: Option<Length>,
+    line_height: Option<Length>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum ThemeBase {
+    #[default]
+    Light,
+    Dark,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    #[serde(default)]
+    base: ThemeBase,
+    border: Option<HexColor>,
+    button_surface: Option<HexColor>,
+    caret: Option<HexColor>,
+    danger: Option<HexColor>,
+    focus_ring: Option<HexColor>,
+    foreground: Option<HexColor>,
+    ground: Option<HexColor>,
+    highlight: Option<HexColor>,
+    muted_foreground: Option<HexColor>,
+    overlay: Option<HexColor>,
+    placeholder: Option<HexColor>,
+    primary: Option<HexColor>,
+    primary_foreground: Option<HexColor>,
+    primary_hover: Option<HexColor>,
+    selection: Option<HexColor>,
+    success: Option<HexColor>,
+    surface: Option<HexColor>,
+    transparent: Option<HexColor>,
+    warning: Option<HexColor>,
+    #[serde(default)]
+    radius: RadiusFile,
+    #[serde(default)]
+    spacing: SpacingFile,
+    #[serde(default)]
+    text: TypographyFile,
+}
+
+impl ThemeFile {
+    fn into_theme(self) -> Theme {
+        let mut theme = match self.base {
+            ThemeBase::Light => Theme::light(),
+            ThemeBase::Dark => Theme::dark(),
+        };
+
+        let tokens = [
+            (self.border, &mut theme.border),
+            (self.button_surface, &mut theme.button_surface),
+            (self.caret, &mut theme.caret),
+            (self.danger, &mut theme.danger),
+            (self.focus_ring, &mut theme.focus_ring),
+            (self.foreground, &mut theme.foreground),
+            (self.ground, &mut theme.ground),
+            (self.highlight, &mut theme.highlight),
+            (self.muted_foreground, &mut theme.muted_foreground),
+            (self.overlay, &mut theme.overlay),
+            (self.placeholder, &mut theme.placeholder),
+            (self.primary, &mut theme.primary),
+            (self.primary_foreground, &mut theme.primary_foreground),
+            (self.primary_hover, &mut theme.primary_hover),
+            (self.selection, &mut theme.selection),
+            (self.success, &mut theme.success),
+            (self.surface, &mut theme.surface),
+            (self.transparent, &mut theme.transparent),
+            (self.warning, &mut theme.warning),
+        ];
+        for (color, token) in tokens {
+            if let Some(HexColor(color)) = color {
+                *token = color;
+            }
+        }
+
+        let lengths = [
+            (self.radius.sm, &mut theme.radius.sm),
+            (self.radius.md, &mut theme.radius.md),
+            (self.radius.lg, &mut theme.radius.lg),
+            (self.spacing.xs, &mut theme.spacing.xs),
+            (self.spacing.sm, &mut theme.spacing.sm),
+            (self.spacing.md, &mut theme.spacing.md),
+            (self.spacing.lg, &mut theme.spacing.lg),
+            (self.spacing.xl, &mut theme.spacing.xl),
+            (self.text.xs, &mut theme.text.xs),
+            (self.text.sm, &mut theme.text.sm),
+            (self.text.md, &mut theme.text.md),
+            (self.text.line_height, &mut theme.text.line_height),
+        ];
+        for (length, token) in lengths {
+            if let Some(Length(length)) = length {
+                *token = length;
+            }
+        }
+
+        theme
+    }
+}
+
+impl Theme {
+    /// Parses a theme from TOML. `base` names the built-in theme (`"light"`
+    /// or `"dark"`) that supplies every token the file leaves out. Colors are
+    /// top-level keys; the `radius`, `spacing` and `text` tables set the
+    /// scales, in pixels.
+    pub fn from_toml(source: &str) -> Result<Self, ThemeError> {
+        toml::from_str::<ThemeFile>(source)
+            .map(ThemeFile::into_theme)
+            .map_err(ThemeError::Toml)
+    }
+
+    /// Parses a theme from JSON, with the same keys as [`Theme::from_toml`].
+    pub fn from_json(source: &str) -> Result<Self, ThemeError> {
+        serde_json::from_str::<ThemeFile>(source)
+            .map(ThemeFile::into_theme)
+            .map_err(ThemeError::Json)
+    }
+
+    /// Reads a `.toml` or `.json` theme file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let parse = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml,
+            Some("json") => Self::from_json,
+            _ => return Err(ThemeError::UnsupportedFormat(path.to_path_buf())),
+        };
+        parse(&fs::read_to_string(path).map_err(ThemeError::Io)?)
+    }
+
+    /// Applies the theme at `path` and re-applies it whenever the file
+    /// changes, until the returned task is dropped. Nothing happens while the
+    /// file doesn't exist; errors are passed to `on_error` and leave the
+    /// current theme in place.
+    pub fn watch(
+        path: impl Into<PathBuf>,
+        cx: &mut App,
+        on_error: impl Fn(ThemeError) + 'static,
+    ) -> Task<()> {
+        let path = path.into();
+        cx.spawn(async move |cx| {
+            let mut last_modified: Option<SystemTime> = None;
+            loop {
+                // The file system is only touched on the background executor,
+                // so a slow disk can't stall the UI.
+                let path = path.clone();
+                let (modified, loaded) = cx
+                    .background_executor()
+                    .spawn(async move {
+                        let modified = fs::metadata(&path)
+                            .and_then(|metadata| metadata.modified())
+                            .ok();
+                        let loaded = (modified.is_some() && modified != last_modified)
+                            .then(|| Self::load(&path));
+                        (modified, loaded)
+                    })
+                    .await;
+                let applied = match loaded {
+                    Some(Ok(theme)) => cx.update(|cx| Self::apply(cx, theme)),
+                    Some(Err(error)) => {
+                        on_error(error);
+                        Ok(())
+                    }
+                    None => Ok(()),
+                };
+                if applied.is_err() {
+                    return;
+                }
+                last_modified = modified;
+
+                cx.background_executor().timer(POLL_INTERVAL).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::rgb;
+
+    fn error(result: Result<Theme, ThemeError>) -> String {
+        match result {
+            Ok(_) => panic!("expected the theme to be rejected"),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn tokens_fall_back_to_the_base_theme() {
+        let theme = Theme::from_toml("base = \"dark\"\nprimary = \"#FF0000\"").unwrap();
+        assert_eq!(theme.primary, rgb(0xFF0000).into());
+        assert_eq!(theme.ground, Theme::dark().ground);
+        assert_eq!(theme.surface, Theme::dark().surface);
+    }
+
+    #[test]
+    fn base_defaults_to_light() {
+        let theme = Theme::from_toml("surface = \"#00000080\"").unwrap();
+        assert_eq!(theme.surface, rgba(0x00000080).into());
+        assert_eq!(theme.foreground, Theme::light().foreground);
+    }
+
+    #[test]
+    fn json_uses_the_same_keys() {
+        let theme = Theme::from_json(r#"{ "base": "dark", "caret": "00FF00" }"#).unwrap();
+        assert_eq!(theme.caret, rgb(0x00FF00).into());
+        assert_eq!(theme.ground, Theme::dark().ground);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(
+            error(Theme::from_toml("primery = \"#FF0000\"")).contains("unknown field `primery`")
+        );
+        assert!(
+            error(Theme::from_json(r##"{ "primery": "#FF0000" }"##))
+                .contains("unknown field `primery`")
+        );
+        assert!(error(Theme::from_toml("base = \"blue\"")).contains("unknown variant `blue`"));
+    }
+
+    #[test]
+    fn malformed_colors_are_rejected() {
+        for color in [
+            "",
+            "#",
+            "#123",
+            "#1234567",
+            "#12345G",
+            "+12345",
+            "#+1234567",
+            "# 12345",
+        ] {
+            let message = error(Theme::from_toml(&format!("primary = \"{color}\"")));
+            assert!(
+                message.contains("expected #RRGGBB or #RRGGBBAA"),
+                "{color}: {message}"
+            );
+        }
+    }
+
+    #[test]
+    fn scales_are_set_from_tables() {
+        let theme = Theme::from_toml("[radius]\nmd = 10\n\n[text]\nline_height = 18.5").unwrap();
+        assert_eq!(theme.radius.md, px(10.));
+        assert_eq!(theme.radius.sm, Theme::light().radius.sm);
+        assert_eq!(theme.text.line_height, px(18.5));
+        assert_eq!(theme.spacing.md, Theme::light().spacing.md);
+
+        let theme = Theme::from_json(r#"{ "spacing": { "xl": 32 } }"#).unwrap();
+        assert_eq!(theme.spacing.xl, px(32.));
+
+        assert!(error(Theme::from_toml("[radius]\nmd = -1")).contains("invalid length -1"));
+        assert!(error(Theme::from_toml("[spacing]\nxxl = 40")).contains("unknown field `xxl`"));
+    }
+
+    #[test]
+    fn load_checks_the_extension() {
+        assert!(matches!(
+            Theme::load("theme.yaml"),
+            Err(ThemeError::UnsupportedFormat(_))
+        ));
+        assert!(matches!(
+            Theme::load("does-not-exist.toml"),
+            Err(ThemeError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,366 @@
+use crate::button::{Button, ButtonVariant};
+use crate::theme::ThemeAble;
+use gpui::{
+    App, Bounds, Corner, Global, IntoElement, ParentElement, Point, RenderOnce, SharedString,
+    Styled, Window, anchored, deferred, div, prelude::*, px,
+};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+const DEFAULT_DURATION: Duration = Duration::from_secs(4);
+const PRIORITY: usize = 30;
+
+type ActionHandler = Rc<dyn Fn(&mut Window, &mut App)>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ToastLevel {
+    #[default]
+    Info,
+    Success,
+    Error,
+}
+
+/// Identifies a toast that has been shown, so it can be dismissed early.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ToastId(usize);
+
+impl ToastId {
+    pub fn dismiss(self, cx: &mut App) {
+        if let Some(toasts) = cx.try_global::<Toasts>()
+            && toasts.position(self).is_some()
+        {
+            cx.global_mut::<Toasts>()
+                .entries
+                .retain(|entry| entry.id != self);
+            cx.refresh_windows();
+        }
+    }
+}
+
+/// A short message shown in the corner of every window that renders a
+/// [`ToastStack`]. It disappears after its duration, which only runs while
+/// the pointer isn't over it.
+#[derive(Clone)]
+pub struct Toast {
+    message: SharedString,
+    level: ToastLevel,
+    duration: Duration,
+    action: Option<(SharedString, ActionHandler)>,
+}
+
+impl Toast {
+    pub fn new(message: impl Into<SharedString>) -> Self {
+        Self {
+            message: message.into(),
+            level: ToastLevel::default(),
+            duration: DEFAULT_DURATION,
+            action: None,
+        }
+    }
+
+    pub fn info(message: impl Into<SharedString>) -> Self {
+        Self::new(message)
+    }
+
+    pub fn success(message: impl Into<SharedString>) -> Self {
+        Self::new(message).level(ToastLevel::Success)
+    }
+
+    pub fn error(message: impl Into<SharedString>) -> Self {
+        Self::new(message).level(ToastLevel::Error)
+    }
+
+    pub fn level(mut self, level: ToastLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Adds a button that runs `handler` and dismisses the toast.
+    pub fn action(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.action = Some((label.into(), Rc::new(handler)));
+        self
+    }
+
+    /// Queues the toast and starts its timer.
+    pub fn show(self, cx: &mut App) -> ToastId {
+        let now = cx.background_executor().now();
+        let toasts = cx.default_global::<Toasts>();
+        let id = ToastId(toasts.next_id);
+        toasts.next_id += 1;
+        let duration = self.duration;
+        toasts.entries.push(Entry {
+            id,
+            toast: self,
+            remaining: duration,
+            resumed_at: Some(now),
+            generation: 0,
+        });
+        expire_after(id, 0, duration, cx);
+        cx.refresh_windows();
+        id
+    }
+}
+
+struct Entry {
+    id: ToastId,
+    toast: Toast,
+    remaining: Duration,
+    // `None` while the timer is paused by the pointer hovering the toast.
+    resumed_at: Option<Instant>,
+    // Bumped on every pause and resume so stale timers can tell they're stale.
+    generation: usize,
+}
+
+#[derive(Default)]
+struct Toasts {
+    next_id: usize,
+    entries: Vec<Entry>,
+}
+
+impl Global for Toasts {}
+
+impl Toasts {
+    fn position(&self, id: ToastId) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.id == id)
+    }
+}
+
+fn expire_after(id: ToastId, generation: usize, duration: Duration, cx: &mut App) {
+    cx.spawn(async move |cx| {
+        cx.background_executor().timer(duration).await;
+        cx.update(|cx| {
+            let current = cx
+                .try_global::<Toasts>()
+                .and_then(|toasts| toasts.entries.iter().find(|entry| entry.id == id))
+                .is_some_and(|entry| entry.generation == generation);
+            if current {
+                id.dismiss(cx);
+            }
+        })
+        .ok();
+    })
+    .detach();
+}
+
+fn set_paused(id: ToastId, paused: bool, cx: &mut App) {
+    let Some(ix) = cx
+        .try_global::<Toasts>()
+        .and_then(|toasts| toasts.position(id))
+    else {
+        return;
+    };
+
+    // The executor's clock is the one the expiry timers run on.
+    let now = cx.background_executor().now();
+    let entry = &mut cx.global_mut::<Toasts>().entries[ix];
+    match (paused, entry.resumed_at) {
+        (true, Some(resumed_at)) => {
+            entry.remaining = entry.remaining.saturating_sub(now - resumed_at);
+            entry.resumed_at = None;
+            entry.generation += 1;
+        }
+        (false, None) => {
+            entry.resumed_at = Some(now);
+            entry.generation += 1;
+            let (generation, remaining) = (entry.generation, entry.remaining);
+            expire_after(id, generation, remaining, cx);
+        }
+        _ => {}
+    }
+}
+
+/// Draws the queued toasts stacked in a corner of the window, the newest
+/// closest to the corner. Add one to each window's root view.
+#[derive(IntoElement)]
+pub struct ToastStack {
+    corner: Corner,
+}
+
+impl Default for ToastStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToastStack {
+    pub fn new() -> Self {
+        Self {
+            corner: Corner::BottomRight,
+        }
+    }
+
+    pub fn corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+}
+
+impl RenderOnce for ToastStack {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let Some(toasts) = cx
+            .try_global::<Toasts>()
+            .filter(|toasts| !toasts.entries.is_empty())
+        else {
+            return div().into_any_element();
+        };
+
+        let theme = cx.theme();
+        let items = toasts.entries.iter().map(|entry| {
+            let id = entry.id;
+            let toast = &entry.toast;
+            let accent = match toast.level {
+                ToastLevel::Info => theme.primary,
+                ToastLevel::Success => theme.success,
+                ToastLevel::Error => theme.danger,
+            };
+
+            div()
+                .id(("toast", id.0))
+                .flex()
+                .items_center()
+                .gap(theme.spacing.md)
+                .max_w(px(320.))
+                .p(theme.spacing.md)
+                .border_1()
+                .border_color(theme.border)
+                .rounded(theme.radius.md)
+                .shadow_md()
+                .bg(theme.surface)
+                .text_size(theme.text.sm)
+                .text_color(theme.foreground)
+                .on_hover(move |hovered, _, cx| set_paused(id, *hovered, cx))
+                .child(div().flex_none().size_2().rounded_full().bg(accent))
+                .child(div().flex_1().child(toast.message.clone()))
+                .when_some(toast.action.clone(), |this, (label, handler)| {
+                    this.child(
+                        Button::new(format!("toast-action-{}", id.0).into())
+                            .variant(ButtonVariant::Ghost)
+                            .with_label(label)
+                            .on_click(move |_, window, cx| {
+                                handler(window, cx);
+                                id.dismiss(cx);
+                            }),
+                    )
+                })
+                .child(
+                    div()
+                        .id(("toast-close", id.0))
+                        .flex_none()
+                        .px(theme.spacing.xs)
+                        .cursor_pointer()
+                        .text_color(theme.muted_foreground)
+                        .hover(|this| this.text_color(theme.foreground))
+                        .child("×")
+                        .on_click(move |_, _, cx| id.dismiss(cx)),
+                )
+        });
+
+        let left = matches!(self.corner, Corner::TopLeft | Corner::BottomLeft);
+        let stack = div()
+            .flex()
+            .flex_col()
+            .when(left, |this| this.items_start())
+            .when(!left, |this| this.items_end())
+            .gap(theme.spacing.sm)
+            .p(theme.spacing.md);
+        let stack = match self.corner {
+            Corner::TopLeft | Corner::TopRight => stack.children(items.rev()),
+            Corner::BottomLeft | Corner::BottomRight => stack.children(items),
+        };
+
+        let position = Bounds::new(Point::default(), window.viewport_size()).corner(self.corner);
+        deferred(
+            anchored()
+                .anchor(self.corner)
+                .position(position)
+                .child(stack),
+        )
+        .with_priority(PRIORITY)
+        .into_any_element()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    fn is_shown(id: ToastId, cx: &TestAppContext) -> bool {
+        cx.read(|cx| {
+            cx.try_global::<Toasts>()
+                .is_some_and(|toasts| toasts.position(id).is_some())
+        })
+    }
+
+    fn advance(duration: Duration, cx: &mut TestAppContext) {
+        cx.executor().advance_clock(duration);
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn expires_after_its_duration(cx: &mut TestAppContext) {
+        let id = cx.update(|cx| Toast::new("Saved").show(cx));
+        advance(DEFAULT_DURATION - Duration::from_millis(1), cx);
+        assert!(is_shown(id, cx));
+        advance(Duration::from_millis(1), cx);
+        assert!(!is_shown(id, cx));
+    }
+
+    #[gpui::test]
+    fn hovering_pauses_the_timer(cx: &mut TestAppContext) {
+        let id = cx.update(|cx| {
+            Toast::new("Saved")
+                .duration(Duration::from_secs(4))
+                .show(cx)
+        });
+        advance(Duration::from_secs(1), cx);
+        cx.update(|cx| set_paused(id, true, cx));
+
+        // The timer started with the toast is stale now.
+        advance(Duration::from_secs(10), cx);
+        assert!(is_shown(id, cx));
+
+        // Resuming runs only the remaining three seconds, and a second resume
+        // doesn't start another timer.
+        cx.update(|cx| set_paused(id, false, cx));
+        cx.update(|cx| set_paused(id, false, cx));
+        advance(Duration::from_millis(2_900), cx);
+        assert!(is_shown(id, cx));
+        advance(Duration::from_millis(100), cx);
+        assert!(!is_shown(id, cx));
+    }
+
+    #[gpui::test]
+    fn dismissing_removes_only_that_toast(cx: &mut TestAppContext) {
+        let (first, second) = cx.update(|cx| {
+            let first = Toast::new("First").show(cx);
+            let second = Toast::error("Second")
+                .duration(Duration::from_secs(10))
+                .show(cx);
+            (first, second)
+        });
+        assert_ne!(first, second);
+
+        cx.update(|cx| {
+            first.dismiss(cx);
+            first.dismiss(cx);
+        });
+        assert!(!is_shown(first, cx));
+        assert!(is_shown(second, cx));
+
+        // The dismissed toast's timer firing leaves the other one alone.
+        advance(DEFAULT_DURATION, cx);
+        assert!(is_shown(second, cx));
+        advance(Duration::from_secs(6), cx);
+        assert!(!is_shown(second, cx));
+    }
+}
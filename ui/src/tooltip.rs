@@ -0,0 +1,311 @@
+use crate::theme::ThemeAble;
+use gpui::{
+    AnyElement, App, Bounds, Corner, ElementId, IntoElement, ParentElement, Pixels, Point,
+    RenderOnce, SharedString, Size, Styled, Window, anchored, deferred, div, point, prelude::*, px,
+};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(500);
+const OFFSET: Pixels = px(4.);
+const MARGIN: Pixels = px(8.);
+const PRIORITY: usize = 40;
+
+/// Which side of the target a [`Tooltip`] prefers. It flips to the opposite
+/// side when there isn't room.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TooltipPlacement {
+    Top,
+    #[default]
+    Bottom,
+    Left,
+    Right,
+}
+
+impl TooltipPlacement {
+    fn opposite(self) -> Self {
+        match self {
+            TooltipPlacement::Top => TooltipPlacement::Bottom,
+            TooltipPlacement::Bottom => TooltipPlacement::Top,
+            TooltipPlacement::Left => TooltipPlacement::Right,
+            TooltipPlacement::Right => TooltipPlacement::Left,
+        }
+    }
+
+    fn fits(self, target: Bounds<Pixels>, size: Size<Pixels>, viewport: Size<Pixels>) -> bool {
+        match self {
+            TooltipPlacement::Top => target.top() - OFFSET - size.height >= MARGIN,
+            TooltipPlacement::Bottom => {
+                target.bottom() + OFFSET + size.height <= viewport.height - MARGIN
+            }
+            TooltipPlacement::Left => target.left() - OFFSET - size.width >= MARGIN,
+            TooltipPlacement::Right => {
+                target.right() + OFFSET + size.width <= viewport.width - MARGIN
+            }
+        }
+    }
+
+    /// This placement, or the opposite one if only that has room for a
+    /// tooltip of `size`.
+    fn resolve(self, target: Bounds<Pixels>, size: Size<Pixels>, viewport: Size<Pixels>) -> Self {
+        if !self.fits(target, size, viewport) && self.opposite().fits(target, size, viewport) {
+            self.opposite()
+        } else {
+            self
+        }
+    }
+
+    /// Where to anchor a tooltip for `target`, flipping to the opposite side
+    /// when it doesn't fit in `viewport`. Until the tooltip has been measured
+    /// this placement is used as is.
+    fn place(
+        self,
+        target: Bounds<Pixels>,
+        size: Option<Size<Pixels>>,
+        viewport: Size<Pixels>,
+    ) -> (Corner, Point<Pixels>) {
+        size.map_or(self, |size| self.resolve(target, size, viewport))
+            .anchor(target)
+    }
+
+    fn anchor(self, target: Bounds<Pixels>) -> (Corner, Point<Pixels>) {
+        match self {
+            TooltipPlacement::Top => (Corner::BottomLeft, target.origin - point(px(0.), OFFSET)),
+            TooltipPlacement::Bottom => (
+                Corner::TopLeft,
+                target.bottom_left() + point(px(0.), OFFSET),
+            ),
+            TooltipPlacement::Left => (Corner::TopRight, target.origin - point(OFFSET, px(0.))),
+            TooltipPlacement::Right => {
+                (Corner::TopLeft, target.top_right() + point(OFFSET, px(0.)))
+            }
+        }
+    }
+}
+
+struct TooltipState {
+    hovered: bool,
+    visible: bool,
+    // Bumped on every hover so a pending delay can tell it has been superseded.
+    generation: usize,
+    target_bounds: Rc<Cell<Option<Bounds<Pixels>>>>,
+    // Measured the first time the tooltip is laid out, which is drawn
+    // transparent so it can be placed before it's seen.
+    size: Rc<Cell<Option<Size<Pixels>>>>,
+}
+
+/// A short text shown next to `target` after the pointer has rested on it.
+///
+/// Hover is tracked on a wrapper around the target, so it also works for
+/// disabled buttons, e.g. to explain why an action isn't available. Pressing
+/// a mouse button hides it.
+#[derive(IntoElement)]
+pub struct Tooltip {
+    id: ElementId,
+    target: AnyElement,
+    text: SharedString,
+    delay: Duration,
+    placement: TooltipPlacement,
+    full_width: bool,
+}
+
+impl Tooltip {
+    pub fn new(id: impl Into<ElementId>, target: impl IntoElement, text: SharedString) -> Self {
+        Self {
+            id: id.into(),
+            target: target.into_any_element(),
+            text,
+            delay: DEFAULT_DELAY,
+            placement: TooltipPlacement::default(),
+            full_width: false,
+        }
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn placement(mut self, placement: TooltipPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Lets the wrapper fill its parent's width, for targets such as a
+    /// full-width [`Button`](crate::Button).
+    pub fn full_width(mut self, full_width: bool) -> Self {
+        self.full_width = full_width;
+        self
+    }
+}
+
+impl RenderOnce for Tooltip {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, _| TooltipState {
+            hovered: false,
+            visible: false,
+            generation: 0,
+            target_bounds: Rc::default(),
+            size: Rc::default(),
+        });
+        let target_bounds = state.read(cx).target_bounds.clone();
+        let size = state.read(cx).size.clone();
+        let visible = state.read(cx).visible;
+
+        let theme = cx.theme();
+        let mut el = div()
+            .on_children_prepainted({
+                let target_bounds = target_bounds.clone();
+                move |bounds, _, _| target_bounds.set(bounds.first().copied())
+            })
+            .id(self.id)
+            .when(self.full_width, |this| this.w_full())
+            .on_hover({
+                let state = state.clone();
+                let delay = self.delay;
+                move |hovered, _, cx| {
+                    let generation = state.update(cx, |state, cx| {
+                        state.hovered = *hovered;
+                        state.visible = false;
+                        state.generation += 1;
+                        cx.notify();
+                        state.generation
+                    });
+                    if !*hovered {
+                        return;
+                    }
+
+                    let state = state.clone();
+                    cx.spawn(async move |cx| {
+                        cx.background_executor().timer(delay).await;
+                        state
+                            .update(cx, |state, cx| {
+                                if state.hovered && state.generation == generation {
+                                    state.visible = true;
+                                    cx.notify();
+                                }
+                            })
+                            .ok();
+                    })
+                    .detach();
+                }
+            })
+            .on_any_mouse_down({
+                let state = state.clone();
+                move |_, _, cx| {
+                    state.update(cx, |state, cx| {
+                        state.visible = false;
+                        state.generation += 1;
+                        cx.notify();
+                    })
+                }
+            })
+            .child(self.target);
+
+        if let Some(bounds) = target_bounds.get().filter(|_| visible) {
+            let measured = size.get();
+            let (corner, position) = self
+                .placement
+                .place(bounds, measured, window.viewport_size());
+            el = el.child(
+                deferred(
+                    anchored()
+                        .anchor(corner)
+                        .position(position)
+                        .snap_to_window_with_margin(MARGIN)
+                        .child(
+                            div()
+                                .on_children_prepainted(move |bounds, window, _| {
+                                    let measured = bounds.first().map(|bounds| bounds.size);
+                                    if size.replace(measured) != measured {
+                                        window.refresh();
+                                    }
+                                })
+                                .child(
+                                    div()
+                                        .max_w(px(240.))
+                                        .px(theme.spacing.md)
+                                        .py(theme.spacing.xs)
+                                        .rounded(theme.radius.sm)
+                                        .shadow_xs()
+                                        .bg(theme.foreground)
+                                        .text_color(theme.surface)
+                                        .text_size(theme.text.xs)
+                                        .when(measured.is_none(), |this| this.opacity(0.))
+                                        .child(self.text),
+                                ),
+                        ),
+                )
+                .with_priority(PRIORITY),
+            );
+        }
+
+        el
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{bounds, size};
+
+    const VIEWPORT: Size<Pixels> = size(px(400.), px(300.));
+    const TOOLTIP: Size<Pixels> = size(px(80.), px(20.));
+
+    fn target(x: f32, y: f32) -> Bounds<Pixels> {
+        bounds(point(px(x), px(y)), size(px(40.), px(20.)))
+    }
+
+    #[test]
+    fn keeps_the_preferred_side_when_it_fits() {
+        let target = target(180., 140.);
+        for placement in [
+            TooltipPlacement::Top,
+            TooltipPlacement::Bottom,
+            TooltipPlacement::Left,
+            TooltipPlacement::Right,
+        ] {
+            assert_eq!(
+                placement.place(target, Some(TOOLTIP), VIEWPORT),
+                placement.anchor(target)
+            );
+        }
+    }
+
+    #[test]
+    fn flips_away_from_each_window_edge() {
+        let cases = [
+            (TooltipPlacement::Top, target(180., 10.)),
+            (TooltipPlacement::Bottom, target(180., 270.)),
+            (TooltipPlacement::Left, target(10., 140.)),
+            (TooltipPlacement::Right, target(350., 140.)),
+        ];
+        for (placement, target) in cases {
+            assert_eq!(
+                placement.place(target, Some(TOOLTIP), VIEWPORT),
+                placement.opposite().anchor(target),
+                "{placement:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn stays_put_when_neither_side_fits() {
+        let viewport = size(px(400.), px(50.));
+        let target = target(180., 15.);
+        assert_eq!(
+            TooltipPlacement::Top.place(target, Some(TOOLTIP), viewport),
+            TooltipPlacement::Top.anchor(target)
+        );
+    }
+
+    #[test]
+    fn uses_the_preferred_side_until_measured() {
+        let target = target(180., 10.);
+        assert_eq!(
+            TooltipPlacement::Top.place(target, None, VIEWPORT),
+            TooltipPlacement::Top.anchor(target)
+        );
+    }
+}
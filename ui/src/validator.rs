@@ -0,0 +1,27 @@
+use gpui::SharedString;
+
+/// Rules a [`crate::TextInput`] checks its content against.
+///
+/// `allows` filters text before it is inserted: an edit containing any rejected
+/// character is dropped. `validate` reports an error for the current content,
+/// which the input shows under the field. While the field is focused, errors
+/// for content that `is_pending` are held back until the user has finished
+/// typing it or leaves the field.
+pub trait Validator {
+    fn allows(&self, ch: char) -> bool {
+        let _ = ch;
+        true
+    }
+
+    fn validate(&self, content: &str) -> Result<(), SharedString> {
+        let _ = content;
+        Ok(())
+    }
+
+    /// Whether `content` is the start of something that could still become
+    /// valid, e.g. a date without its year.
+    fn is_pending(&self, content: &str) -> bool {
+        let _ = content;
+        false
+    }
+}